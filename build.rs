@@ -0,0 +1,67 @@
+//! Generates the registry of solutions from the files in `src/bin`.
+//!
+//! Every `src/bin/NN.rs` file is included in the library as the `solutions::dayNN` module,
+//! so all the days can be run in-process by a single binary.
+//! The solutions of the other years, `src/bin/YYYY-NN.rs`, are included as `solutions::yYYYY_dayNN`.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let is_number = |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+
+    // NOTE: the solutions of the default year have no year, and are sorted first.
+    let mut days: Vec<(Option<String>, String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let (year, day) = match path.file_stem()?.to_str()?.split_once('-') {
+                Some((year, day)) => (Some(year), day),
+                None => (None, path.file_stem()?.to_str()?),
+            };
+            let is_day = is_number(day, 2) && year.is_none_or(|year| is_number(year, 4));
+            is_day.then(|| {
+                let year = year.map(str::to_owned);
+                (year, day.to_owned(), path.display().to_string())
+            })
+        })
+        .collect();
+
+    days.sort_unstable();
+
+    let mut registry = String::from("// @generated by `build.rs` from the files in `src/bin`.\n\n");
+
+    let get_module = |year: &Option<String>, day: &str| match year {
+        Some(year) => format!("y{year}_day{day}"),
+        None => format!("day{day}"),
+    };
+
+    for (year, day, path) in &days {
+        let module = get_module(year, day);
+        registry.push_str(&format!("#[path = {path:?}]\npub mod {module};\n"));
+    }
+
+    registry.push_str("\n/// The solutions of every scaffolded day, with their year if it is not the default one.\n");
+    registry.push_str("/// Sorted by year, then by day.\n");
+    registry.push_str(
+        "pub static SOLUTIONS: &[(Option<crate::template::Year>, crate::template::Solution)] = &[\n",
+    );
+    for (year, day, _) in &days {
+        let module = get_module(year, day);
+        let year = match year {
+            Some(year) => format!("Some(crate::year!({year}))"),
+            None => "None".into(),
+        };
+        registry.push_str(&format!("    ({year}, {module}::SOLUTION),\n"));
+    }
+    registry.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, registry).unwrap();
+}
@@ -1,8 +1,8 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse = parse_input);
 
 /* -------------------------------------------------------------------------- */
 
-struct Game {
+pub struct Game {
     id: u32,
     draw: Vec<Draw>,
 }
@@ -51,11 +51,9 @@ fn parse_input(input: &str) -> Vec<Game> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = parse_input(input);
-
+pub fn part_one(games: &[Game]) -> Option<u32> {
     let result = games
-        .into_iter()
+        .iter()
         .filter(|game| {
             game.draw
                 .iter()
@@ -70,15 +68,14 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = parse_input(input);
-
+pub fn part_two(games: &[Game]) -> Option<u32> {
     let result = games
-        .into_iter()
+        .iter()
         .map(|game| {
             let minimum_colors = game
                 .draw
-                .into_iter()
+                .iter()
+                .copied()
                 .reduce(|acc, e| Draw {
                     red: acc.red.max(e.red),
                     green: acc.green.max(e.green),
@@ -101,13 +98,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(2286));
     }
 }
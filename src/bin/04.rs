@@ -1,11 +1,11 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse = parse_input);
 
 /* -------------------------------------------------------------------------- */
 
 #[derive(Clone, Copy)]
-struct Card {
+pub struct Card {
     match_count: usize,
 }
 
@@ -32,11 +32,9 @@ fn parse_input(input: &str) -> Vec<Card> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let cards = parse_input(input);
-
+pub fn part_one(cards: &[Card]) -> Option<u32> {
     let result = cards
-        .into_iter()
+        .iter()
         .map(|card| card.match_count.checked_sub(1).map(|x| 1 << x).unwrap_or(0))
         .sum();
 
@@ -45,12 +43,10 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let cards = parse_input(input);
-
+pub fn part_two(cards: &[Card]) -> Option<u32> {
     let mut card_count = vec![1; cards.len()];
 
-    for (i, card) in cards.into_iter().enumerate() {
+    for (i, card) in cards.iter().enumerate() {
         let count = card_count[i];
         for j in 0..card.match_count {
             card_count[i + 1 + j] += count;
@@ -70,13 +66,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(30));
     }
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse_input);
 
 /* -------------------------------------------------------------------------- */
 
@@ -103,7 +103,7 @@ impl ResourceMap {
     }
 }
 
-struct Maps {
+pub struct Maps {
     seed_to_soil: ResourceMap,
     soil_to_fertilizer: ResourceMap,
     fertilizer_to_water: ResourceMap,
@@ -113,7 +113,7 @@ struct Maps {
     humidity_to_location: ResourceMap,
 }
 
-fn parse_input(input: &str) -> (impl Iterator<Item = u64> + '_, Maps) {
+fn parse_input(input: &str) -> (Vec<u64>, Maps) {
     let mut lines = input.lines();
 
     let seeds = lines
@@ -122,7 +122,8 @@ fn parse_input(input: &str) -> (impl Iterator<Item = u64> + '_, Maps) {
         .strip_prefix("seeds:")
         .unwrap()
         .split_whitespace()
-        .map(|x| x.parse().unwrap());
+        .map(|x| x.parse().unwrap())
+        .collect();
     let _ = lines.next(); // empty line
 
     macro_rules! parse_resource_map {
@@ -178,11 +179,10 @@ fn parse_map_range(str: &str) -> MapRange {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (seeds, maps) = parse_input(input);
-
+pub fn part_one((seeds, maps): &(Vec<u64>, Maps)) -> Option<u64> {
     let locations = seeds
-        .map(|seed| maps.seed_to_soil.map(seed))
+        .iter()
+        .map(|&seed| maps.seed_to_soil.map(seed))
         .map(|soil| maps.soil_to_fertilizer.map(soil))
         .map(|fertilizer| maps.fertilizer_to_water.map(fertilizer))
         .map(|water| maps.water_to_light.map(water))
@@ -219,10 +219,12 @@ pub fn part_one(input: &str) -> Option<u64> {
 //     Some(result)
 // }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (seeds, maps) = parse_input(input);
-
-    let seeds = seeds.tuples().map(|(start, len)| start..(start + len));
+pub fn part_two((seeds, maps): &(Vec<u64>, Maps)) -> Option<u64> {
+    let seeds = seeds
+        .iter()
+        .copied()
+        .tuples()
+        .map(|(start, len)| start..(start + len));
 
     let locations = seeds
         .flat_map(|seeds| maps.seed_to_soil.map_range(seeds))
@@ -247,13 +249,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(46));
     }
 }
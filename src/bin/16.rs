@@ -5,7 +5,7 @@ use advent_of_code::helper::{
     carte::{Direction, Pos},
 };
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, parse = parse_input);
 
 /* -------------------------------------------------------------------------- */
 
@@ -88,11 +88,9 @@ fn process(grid: &Array2D<u8>, start: Beam) -> usize {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let grid = parse_input(input);
-
+pub fn part_one(grid: &Array2D<u8>) -> Option<usize> {
     let result = process(
-        &grid,
+        grid,
         Beam {
             position: Pos { x: 0, y: 0 },
             direction: Direction::Right,
@@ -104,9 +102,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let grid = parse_input(input);
-
+pub fn part_two(grid: &Array2D<u8>) -> Option<usize> {
     let width = grid.width();
     let height = grid.height();
 
@@ -114,14 +110,14 @@ pub fn part_two(input: &str) -> Option<usize> {
         .flat_map(|x| {
             [
                 process(
-                    &grid,
+                    grid,
                     Beam {
                         position: Pos { x, y: 0 },
                         direction: Direction::Down,
                     },
                 ),
                 process(
-                    &grid,
+                    grid,
                     Beam {
                         position: Pos { x, y: height - 1 },
                         direction: Direction::Up,
@@ -132,14 +128,14 @@ pub fn part_two(input: &str) -> Option<usize> {
         .chain((0..height).flat_map(|y| {
             [
                 process(
-                    &grid,
+                    grid,
                     Beam {
                         position: Pos { x: 0, y },
                         direction: Direction::Right,
                     },
                 ),
                 process(
-                    &grid,
+                    grid,
                     Beam {
                         position: Pos { x: width - 1, y },
                         direction: Direction::Left,
@@ -161,13 +157,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(46));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(51));
     }
 }
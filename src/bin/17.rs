@@ -8,7 +8,7 @@ use advent_of_code::helper::{
 };
 use priority_queue::PriorityQueue;
 
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, parse = parse_input);
 
 type Int = u32;
 
@@ -24,9 +24,7 @@ fn parse_input(input: &str) -> Array2D<u32> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_one(input: &str) -> Option<Int> {
-    let grid = parse_input(input);
-
+pub fn part_one(grid: &Array2D<u32>) -> Option<Int> {
     #[derive(Hash, Eq, PartialEq, Clone, Copy)]
     struct Node {
         heat: Int,
@@ -172,9 +170,7 @@ pub fn part_one(input: &str) -> Option<Int> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_two(input: &str) -> Option<Int> {
-    let grid = parse_input(input);
-
+pub fn part_two(grid: &Array2D<u32>) -> Option<Int> {
     #[derive(Hash, Eq, PartialEq, Clone, Copy)]
     struct Node {
         heat: Int,
@@ -328,18 +324,22 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(102));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(94));
 
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&parse_input(&advent_of_code::template::read_file_part(
             "examples", DAY, 21,
-        ));
+        )));
         assert_eq!(result, Some(71));
     }
 }
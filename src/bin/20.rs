@@ -2,13 +2,14 @@ use std::collections::{HashMap, VecDeque};
 
 use itertools::Itertools;
 
-advent_of_code::solution!(20, 1);
+advent_of_code::solution!(20, parse = parse_input, 1);
 
 type Int = u32;
 
 /* -------------------------------------------------------------------------- */
 
-struct Module {
+#[derive(Clone)]
+pub struct Module {
     destinations: Box<[Box<str>]>,
     kind: ModuleKind,
 }
@@ -85,8 +86,9 @@ fn parse_input(input: &str) -> HashMap<Box<str>, Module> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_one(input: &str) -> Option<Int> {
-    let mut modules = parse_input(input);
+pub fn part_one(modules: &HashMap<Box<str>, Module>) -> Option<Int> {
+    // NOTE: the modules hold the state of the circuit, which the button presses change.
+    let mut modules = modules.clone();
 
     let mut queue = VecDeque::new();
 
@@ -163,8 +165,8 @@ pub fn part_one(input: &str) -> Option<Int> {
 
 /* -------------------------------------------------------------------------- */
 
-pub fn part_two(input: &str) -> Option<Int> {
-    let mut modules = parse_input(input);
+pub fn part_two(modules: &HashMap<Box<str>, Module>) -> Option<Int> {
+    let mut modules = modules.clone();
 
     let mut queue = VecDeque::new();
 
@@ -241,14 +243,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&parse_input(&advent_of_code::template::read_file_part(
             "examples", DAY, 10,
-        ));
+        )));
         assert_eq!(result, Some(32_000_000));
 
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&parse_input(&advent_of_code::template::read_file_part(
             "examples", DAY, 11,
-        ));
+        )));
         assert_eq!(result, Some(11_687_500));
     }
 }
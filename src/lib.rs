@@ -1,5 +1,10 @@
 #![feature(hash_set_entry)]
 
+// NOTE: allows the solutions, which refer to `advent_of_code::...`, to be compiled in the library.
+extern crate self as advent_of_code;
+
 pub mod template;
 
 pub mod helper;
+
+pub mod solutions;
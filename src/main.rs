@@ -1,71 +1,175 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, report, scaffold, solve, stars, time,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{process, time::Duration};
 
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        export::ExportFormat,
+        history::{parse_percentage, DEFAULT_REGRESSION_THRESHOLD},
+        input::InputSource,
+        parse_timeout, Day, Year,
+    };
 
     pub enum AppArguments {
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
+            force: bool,
+        },
+        Examples {
+            day: Day,
+            force: bool,
         },
         Scaffold {
             day: Day,
         },
+        Stars,
+        Report {
+            output: Option<String>,
+        },
         Solve {
             day: Day,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            input: InputSource,
         },
         All {
             release: bool,
             time: bool,
+            isolated: bool,
+            timeout: Option<Duration>,
+            jobs: Option<usize>,
+            days: Vec<Day>,
         },
         Time {
             all: bool,
             no_readme: bool,
+            chart: bool,
+            isolated: bool,
+            timeout: Option<Duration>,
+            jobs: Option<usize>,
+            regression_threshold: f64,
+            save_baseline: Option<String>,
+            baseline: Option<String>,
+            export: Option<(ExportFormat, String)>,
             day: Option<Day>,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let subcommand = args.subcommand()?;
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
+        // NOTE: every command works on the selected year, and so do the solutions they run.
+        if let Some(year) = args.opt_value_from_str::<_, Year>("--year")? {
+            year.select();
+        }
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let release = args.contains("--release");
+                let time = args.contains("--time");
+                let isolated = args.contains("--isolated");
+                let timeout = args.opt_value_from_fn("--timeout", parse_timeout)?;
+                let jobs = args.opt_value_from_str("--jobs")?;
+
+                let mut days = vec![];
+                while let Some(day) = args.opt_free_from_str()? {
+                    days.push(day);
+                }
+
+                AppArguments::All {
+                    release,
+                    time,
+                    isolated,
+                    timeout,
+                    jobs,
+                    days,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let no_readme = args.contains("--no-readme");
+                let chart = args.contains("--chart");
+                let isolated = args.contains("--isolated");
+                let timeout = args.opt_value_from_fn("--timeout", parse_timeout)?;
+                let jobs = args.opt_value_from_str("--jobs")?;
+                let regression_threshold = args
+                    .opt_value_from_fn("--regression-threshold", parse_percentage)?
+                    .unwrap_or(DEFAULT_REGRESSION_THRESHOLD);
+                let save_baseline = args.opt_value_from_str("--save-baseline")?;
+                let baseline = args.opt_value_from_str("--baseline")?;
+
+                // the export path is a free argument, next to the optional day.
+                let (export, day) = match args.opt_value_from_str::<_, ExportFormat>("--export")? {
+                    Some(format) => {
+                        let mut free: Vec<String> = vec![];
+                        while let Some(arg) = args.opt_free_from_str()? {
+                            free.push(arg);
+                        }
+                        let day = free.iter().position(|arg| arg.parse::<Day>().is_ok());
+                        let day = day.map(|i| free.remove(i).parse::<Day>()).transpose()?;
+                        let path = free.pop().ok_or("missing path for --export")?;
+                        (Some((format, path)), day)
+                    }
+                    None => (None, args.opt_free_from_str()?),
+                };
 
                 AppArguments::Time {
                     all,
                     no_readme,
-                    day: args.opt_free_from_str()?,
+                    chart,
+                    isolated,
+                    timeout,
+                    jobs,
+                    regression_threshold,
+                    save_baseline,
+                    baseline,
+                    export,
+                    day,
                 }
             }
             Some("download") => AppArguments::Download {
+                force: args.contains("--force"),
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                force: args.contains("--force"),
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("examples") => AppArguments::Examples {
+                force: args.contains("--force"),
                 day: args.free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
+            Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
             },
+            Some("stars") => AppArguments::Stars,
+            Some("report") => AppArguments::Report {
+                output: args.opt_value_from_str("--output")?,
+            },
+            Some("solve") => {
+                let input = InputSource::new(
+                    args.opt_value_from_str("--input")?,
+                    args.contains("--example"),
+                    args.opt_value_from_str("--example-part")?,
+                )?;
+
+                AppArguments::Solve {
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    time: args.contains("--time"),
+                    input,
+                    day: args.free_from_str()?,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -92,21 +196,54 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                isolated,
+                timeout,
+                jobs,
+                days,
+            } => all::handle(days, release, time, isolated, timeout, jobs),
             AppArguments::Time {
                 day,
                 all,
                 no_readme,
-            } => time::handle(day, all, !no_readme),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+                chart,
+                isolated,
+                timeout,
+                jobs,
+                regression_threshold,
+                save_baseline,
+                baseline,
+                export,
+            } => time::handle(
+                day,
+                time::TimeOptions {
+                    recreate_all: all,
+                    update_readme: !no_readme,
+                    update_chart: chart,
+                    is_isolated: isolated,
+                    timeout,
+                    jobs,
+                    regression_threshold,
+                    save_baseline,
+                    baseline,
+                    export,
+                },
+            ),
+            AppArguments::Download { day, force } => download::handle(day, force),
+            AppArguments::Read { day, force } => read::handle(day, force),
+            AppArguments::Examples { day, force } => examples::handle(day, force),
             AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Report { output } => report::handle(output),
             AppArguments::Solve {
                 day,
                 release,
                 time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                input,
+            } => solve::handle(day, release, time, submit, input),
         },
     };
 }
@@ -0,0 +1,37 @@
+//! Registry of the solutions of every day, so they can be run in-process.
+//!
+//! The modules are generated by `build.rs` from the files in `src/bin`:
+//! the same files are compiled both as standalone binaries and as part of the library.
+use crate::template::{Day, Solution, Year};
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+/// Returns the solution of the given day of the current year, if it has been scaffolded.
+pub fn get(day: Day) -> Option<&'static Solution> {
+    let year = Year::current().filter(|year| !year.is_default());
+    SOLUTIONS
+        .iter()
+        .find(|(solution_year, solution)| *solution_year == year && solution.day == day)
+        .map(|(_, solution)| solution)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{day, template::read_file};
+
+    #[test]
+    fn runs_solutions_in_process() {
+        let solution = super::get(day!(2)).unwrap();
+        let input = read_file("examples", solution.day);
+
+        let mut reports = vec![];
+        (solution.run)(&input, false, &mut |report| reports.push(report));
+        let steps: Vec<_> = reports.iter().map(|r| r.step).collect();
+        let answers: Vec<_> = reports.iter().filter_map(|r| r.answer.as_deref()).collect();
+
+        assert_eq!(steps, solution.steps);
+        assert_eq!(answers, ["8", "2286"]);
+    }
+}
@@ -0,0 +1,118 @@
+/// Module that stores the accepted answers of each part, to detect regressions.
+///
+/// Answers live in `data/answers/<day>-<part>.txt`. They are written when a submission is accepted,
+/// and can also be created by hand.
+use std::{fmt::Display, fs, io, path::PathBuf};
+
+use super::submissions::{SubmissionOutcome, Verdict};
+use super::{data_dir, Day};
+
+pub fn get_answers_dir() -> PathBuf {
+    data_dir().join("answers")
+}
+
+/// The result of checking an answer against the stored one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// The answer matches the stored one.
+    Match,
+    /// The answer differs from the stored one.
+    Mismatch { expected: String },
+    /// There is no stored answer for this part.
+    Unknown,
+}
+
+impl Check {
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, Check::Mismatch { .. })
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Match => f.write_str("✔"),
+            Check::Mismatch { expected } if expected.contains('\n') => f.write_str("✘"),
+            Check::Mismatch { expected } => write!(f, "✘ expected {expected}"),
+            Check::Unknown => f.write_str("?"),
+        }
+    }
+}
+
+#[must_use]
+pub fn get_answer_path(day: Day, part: u8) -> PathBuf {
+    get_answers_dir().join(format!("{day}-{part}.txt"))
+}
+
+/// Reads the stored answer of a part, if any.
+pub fn read_answer(day: Day, part: u8) -> Option<String> {
+    fs::read_to_string(get_answer_path(day, part))
+        .ok()
+        .map(|answer| answer.trim_end().to_owned())
+}
+
+/// Stores the answer of a part, overwriting the previous one.
+pub fn store_answer(day: Day, part: u8, answer: &str) -> Result<(), io::Error> {
+    fs::create_dir_all(get_answers_dir())?;
+    fs::write(get_answer_path(day, part), format!("{answer}\n"))
+}
+
+/// Stores the answer of a part if the submission was correct, returns whether it was.
+pub fn record_answer(
+    day: Day,
+    part: u8,
+    answer: &str,
+    outcome: SubmissionOutcome,
+) -> Result<bool, io::Error> {
+    if outcome != SubmissionOutcome::Judged(Verdict::Correct) {
+        return Ok(false);
+    }
+    store_answer(day, part, answer)?;
+    Ok(true)
+}
+
+/// Checks an answer against the stored one.
+pub fn check_answer(day: Day, part: u8, answer: &str) -> Check {
+    match read_answer(day, part) {
+        Some(expected) => compare(answer, expected),
+        None => Check::Unknown,
+    }
+}
+
+fn compare(answer: &str, expected: String) -> Check {
+    if answer.trim_end() == expected {
+        Check::Match
+    } else {
+        Check::Mismatch { expected }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, Check};
+
+    #[test]
+    fn handles_matching_answers() {
+        assert_eq!(compare("42", "42".into()), Check::Match);
+        assert_eq!(compare("42\n", "42".into()), Check::Match);
+    }
+
+    #[test]
+    fn handles_mismatching_answers() {
+        let check = compare("41", "42".into());
+        assert_eq!(check.is_mismatch(), true);
+        assert_eq!(check.to_string(), "✘ expected 42");
+    }
+
+    #[test]
+    fn displays_checks() {
+        assert_eq!(Check::Match.to_string(), "✔");
+        assert_eq!(Check::Unknown.to_string(), "?");
+        let multiline = Check::Mismatch {
+            expected: "#.\n.#".into(),
+        };
+        assert_eq!(multiline.to_string(), "✘");
+    }
+}
@@ -0,0 +1,421 @@
+/// Module that talks to the Advent of Code website: it downloads the inputs and the puzzle descriptions,
+/// and submits the answers.
+///
+/// The requests are authenticated with the session cookie of the website, read from the `AOC_SESSION`
+/// environment variable or from the `~/.adventofcode.session` file used by aoc-cli.
+/// The website can be replaced with another server by setting `AOC_BASE_URL`, e.g. a mock server in the tests.
+///
+/// To follow the automation guidelines of the website, the requests are spaced by a few seconds,
+/// even across runs: the time of the last request is stored in `data/.last_request`.
+/// The guidelines also ask for a way to contact whoever runs the tool, set in `AOC_USER_AGENT`,
+/// e.g. the url of the repository and an email address.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{Day, Year};
+
+pub static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+static THROTTLE_FILE_PATH: &str = "./data/.last_request";
+const THROTTLE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    /// The server answered with an error status, e.g. 404 for a puzzle that is not unlocked yet.
+    Status(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "session cookie not found. Set AOC_SESSION or write it to ~/{SESSION_FILE_NAME}."
+            ),
+            AocClientError::YearNotSet => write!(f, "AOC_YEAR is not set."),
+            AocClientError::Status(status) => {
+                write!(f, "the server answered with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "the request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::Status(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Spaces the requests by a minimum interval, using a file to remember the time of the last request.
+#[derive(Clone, Debug)]
+pub struct Throttle {
+    pub path: PathBuf,
+    pub interval: Duration,
+}
+
+impl Throttle {
+    /// Waits until the interval has passed since the last request, then records a new request.
+    fn wait(&self) -> Result<(), io::Error> {
+        let last = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok());
+
+        let delay = get_delay(last, get_timestamp_millis(), self.interval);
+        if !delay.is_zero() {
+            eprintln!("Waiting {delay:.1?} before the next request...");
+            thread::sleep(delay);
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, get_timestamp_millis().to_string())
+    }
+}
+
+/// The time left to wait before the next request, given the time of the last one in milliseconds.
+fn get_delay(last: Option<u64>, now: u64, interval: Duration) -> Duration {
+    let Some(last) = last else {
+        return Duration::ZERO;
+    };
+    let elapsed = Duration::from_millis(now.saturating_sub(last));
+    interval.saturating_sub(elapsed)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn get_timestamp_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// A client for the puzzles of one year.
+#[derive(Debug)]
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+    user_agent: String,
+    throttle: Option<Throttle>,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        AocClient {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+            user_agent: DEFAULT_USER_AGENT.into(),
+            throttle: None,
+        }
+    }
+
+    /// Identifies the requests of this client, the default one only names the crate.
+    #[must_use]
+    pub fn with_user_agent(self, user_agent: &str) -> Self {
+        AocClient {
+            user_agent: user_agent.trim().into(),
+            ..self
+        }
+    }
+
+    /// Spaces the requests of this client, see [`Throttle`].
+    #[must_use]
+    pub fn with_throttle(self, throttle: Throttle) -> Self {
+        AocClient {
+            throttle: Some(throttle),
+            ..self
+        }
+    }
+
+    /// Builds a client from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let year = Year::current().ok_or(AocClientError::YearNotSet)?;
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let throttle = Throttle {
+            path: Path::new(THROTTLE_FILE_PATH).into(),
+            interval: THROTTLE_INTERVAL,
+        };
+        let client = AocClient::new(&base_url, &session, year.into_inner()).with_throttle(throttle);
+        Ok(match env::var("AOC_USER_AGENT") {
+            Ok(user_agent) => client.with_user_agent(&user_agent),
+            Err(_) => client,
+        })
+    }
+
+    fn get_day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn throttle(&self) -> Result<(), AocClientError> {
+        if let Some(throttle) = &self.throttle {
+            throttle.wait()?;
+        }
+        Ok(())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        self.throttle()?;
+        let response = self
+            .agent
+            .get(url)
+            .set("User-Agent", &self.user_agent)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Downloads the puzzle input of a day.
+    pub fn get_input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.get_day_url(day)))
+    }
+
+    /// Downloads the description of a day, converted to markdown.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.get_day_url(day))?;
+        Ok(to_markdown(&html))
+    }
+
+    /// Submits the answer of a part and returns the response of the website, converted to markdown.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        self.throttle()?;
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.get_day_url(day)))
+            .set("User-Agent", &self.user_agent)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        Ok(to_markdown(&response.into_string()?))
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+    Some(session)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the `<article>` elements of a page of the website to markdown.
+///
+/// Only handles the few elements used by the puzzle descriptions: headings, paragraphs, lists, links,
+/// code blocks and emphasis. An emphasized code span, which usually is the answer to an example,
+/// becomes `` *`answer`* ``.
+pub fn to_markdown(html: &str) -> String {
+    // NOTE: emphasis cannot be nested in a code span.
+    let html = html
+        .replace("<code><em>", "<em><code>")
+        .replace("</em></code>", "</code></em>");
+
+    let mut markdown = String::new();
+    let mut rest = html.as_str();
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        let article = &rest[start..start + end];
+        // skips the opening tag of the article itself.
+        let content = article.find('>').map_or("", |i| &article[i + 1..]);
+        convert_article(content, &mut markdown);
+        rest = &rest[start + end..];
+    }
+
+    let markdown = markdown.trim_end();
+    if markdown.is_empty() {
+        String::new()
+    } else {
+        format!("{markdown}\n")
+    }
+}
+
+fn convert_article(html: &str, markdown: &mut String) {
+    let mut is_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(rest, is_pre, markdown);
+            break;
+        };
+        push_text(&rest[..start], is_pre, markdown);
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => markdown.push_str("## "),
+            ("h2" | "p", true) => markdown.push_str("\n\n"),
+            ("li" | "ul", true) => markdown.push('\n'),
+            ("pre", false) => {
+                is_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                is_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !is_pre => markdown.push('`'),
+            ("em", _) if !is_pre => markdown.push('*'),
+            ("li", false) => markdown.push_str("- "),
+            ("a", false) => {
+                links.push(get_attribute(tag, "href").unwrap_or_default());
+                markdown.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_text(text: &str, is_pre: bool, markdown: &mut String) {
+    let text = decode_entities(text);
+    if is_pre {
+        markdown.push_str(&text);
+        return;
+    }
+
+    let text = text.replace('\n', " ");
+    // the whitespace between blocks is not part of the text.
+    if markdown.is_empty() || markdown.ends_with('\n') {
+        markdown.push_str(text.trim_start());
+    } else {
+        markdown.push_str(&text);
+    }
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{get_delay, to_markdown};
+
+    #[test]
+    fn spaces_requests() {
+        let interval = Duration::from_secs(5);
+        assert_eq!(get_delay(None, 10_000, interval), Duration::ZERO);
+        assert_eq!(
+            get_delay(Some(8_000), 10_000, interval),
+            Duration::from_secs(3)
+        );
+        assert_eq!(get_delay(Some(1_000), 10_000, interval), Duration::ZERO);
+        // a clock that went backwards waits for the whole interval at most.
+        assert_eq!(get_delay(Some(20_000), 10_000, interval), interval);
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with
+the <a href="/2023/about">global snow production</a>.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>In this example, the values are <code>12</code> and <code>38</code>, for a total of <code><em>50</em></code>.</p>
+<ul><li>First &amp; <code>a &lt; b</code></li><li>Second</li></ul>
+</article>
+<p>Your puzzle answer was <code>142</code>.</p>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "Something is *wrong* with the [global snow production](/2023/about).",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "In this example, the values are `12` and `38`, for a total of *`50`*.",
+            "",
+            "- First & `a < b`",
+            "- Second",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(to_markdown(html), expected);
+    }
+
+    #[test]
+    fn converts_every_article() {
+        let html = "<article><p>Part one.</p></article><p>-</p><article><p>Part two.</p></article>";
+        assert_eq!(to_markdown(html), "Part one.\n\nPart two.\n");
+    }
+
+    #[test]
+    fn converts_pages_without_articles() {
+        assert_eq!(to_markdown("<html></html>"), "");
+    }
+}
@@ -0,0 +1,190 @@
+/// Module that saves named snapshots of the timings, to compare a later run against them.
+///
+/// Baselines are stored in `data/baselines/<name>.json`, in the same format as `data/timings.json`.
+use std::{fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{
+    data_dir,
+    protocol::Step,
+    timings::{StepTiming, Timings},
+    Day,
+};
+
+fn get_baselines_dir() -> PathBuf {
+    data_dir().join("baselines")
+}
+
+fn get_baseline_path(name: &str) -> Result<PathBuf, String> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !name.starts_with('.');
+
+    if is_valid {
+        Ok(get_baselines_dir().join(format!("{name}.json")))
+    } else {
+        Err(format!(
+            "invalid baseline name `{name}`, only letters, digits, `-`, `_` and `.` are allowed."
+        ))
+    }
+}
+
+/// Stores timings as a named baseline, overwriting the previous one.
+pub fn save(name: &str, timings: &Timings) -> Result<(), String> {
+    let path = get_baseline_path(name)?;
+    fs::create_dir_all(get_baselines_dir()).map_err(|e| e.to_string())?;
+    timings
+        .store_file_at(&path)
+        .map_err(|e: io::Error| e.to_string())
+}
+
+/// Reads a named baseline.
+pub fn load(name: &str) -> Result<Timings, String> {
+    let path = get_baseline_path(name)?;
+    Timings::read_from_path(&path)
+        .map_err(|e| format!("could not read baseline `{name}` ({}): {e}", path.display()))
+}
+
+/// The durations of a step in the baseline and in the new run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub step: Step,
+    pub old: Option<Duration>,
+    pub new: Option<Duration>,
+}
+
+impl Comparison {
+    /// The relative change from the baseline, in percent. Negative values are improvements.
+    pub fn change(&self) -> Option<f64> {
+        #[allow(clippy::cast_precision_loss)]
+        let (old, new) = (self.old?.as_nanos() as f64, self.new?.as_nanos() as f64);
+        Some((new - old) / old * 100.0)
+    }
+}
+
+/// Compares the steps of the days in `new` with the baseline.
+pub fn compare(baseline: &Timings, new: &Timings) -> Vec<Comparison> {
+    new.data
+        .iter()
+        .flat_map(|timing| {
+            let old = baseline.data.iter().find(|t| t.day == timing.day);
+
+            [Step::Parse, Step::Part(1), Step::Part(2)]
+                .into_iter()
+                .filter_map(move |step| {
+                    let comparison = Comparison {
+                        day: timing.day,
+                        step,
+                        old: old.and_then(|t| t.step(step)).map(StepTiming::duration),
+                        new: timing.step(step).map(StepTiming::duration),
+                    };
+                    (comparison.old.is_some() || comparison.new.is_some()).then_some(comparison)
+                })
+        })
+        .collect()
+}
+
+/// Formats the comparisons as a table, with one row per step.
+pub fn format_table(name: &str, comparisons: &[Comparison]) -> String {
+    let duration = |d: Option<Duration>| d.map_or("-".into(), |d| format!("{d:.1?}"));
+
+    let mut table = format!(
+        "{:<4} {:<7} {:>12} {:>12} {:>9}\n",
+        "Day", "Step", name, "New", "Change"
+    );
+
+    for comparison in comparisons {
+        let change = comparison
+            .change()
+            .map_or("-".into(), |change| format!("{change:+.1}%"));
+
+        table.push_str(&format!(
+            "{:<4} {:<7} {:>12} {:>12} {:>9}\n",
+            comparison.day.to_string(),
+            comparison.step.to_string(),
+            duration(comparison.old),
+            duration(comparison.new),
+            change
+        ));
+    }
+
+    table
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{compare, format_table, get_baseline_path, Comparison};
+    use crate::{
+        day,
+        template::{
+            protocol::Step,
+            timings::{StepTiming, Timing, Timings},
+        },
+    };
+
+    fn timing(day: u8, part_1: Option<u64>, part_2: Option<u64>) -> Timing {
+        let step = |micros: Option<u64>| {
+            micros.map(|micros| StepTiming::from_duration(Duration::from_micros(micros)))
+        };
+        Timing {
+            part_1: step(part_1),
+            part_2: step(part_2),
+            ..Timing::new(crate::template::Day::new(day).unwrap())
+        }
+    }
+
+    #[test]
+    fn compares_timings() {
+        let baseline = Timings {
+            data: vec![timing(1, Some(100), Some(200)), timing(2, Some(10), None)],
+            history: vec![],
+        };
+        let new = Timings {
+            data: vec![timing(1, Some(50), Some(300)), timing(3, Some(10), None)],
+            history: vec![],
+        };
+
+        let comparisons = compare(&baseline, &new);
+        assert_eq!(comparisons.len(), 3);
+        assert_eq!(comparisons[0].step, Step::Part(1));
+        assert_eq!(comparisons[0].change(), Some(-50.0));
+        assert_eq!(comparisons[1].change(), Some(50.0));
+        assert_eq!(comparisons[2].day, day!(3));
+        assert_eq!(comparisons[2].old, None);
+        assert_eq!(comparisons[2].change(), None);
+    }
+
+    #[test]
+    fn formats_tables() {
+        let comparisons = [Comparison {
+            day: day!(1),
+            step: Step::Part(1),
+            old: Some(Duration::from_micros(100)),
+            new: Some(Duration::from_micros(50)),
+        }];
+
+        assert_eq!(
+            format_table("before", &comparisons),
+            [
+                "Day  Step          before          New    Change",
+                "01   Part 1       100.0µs       50.0µs    -50.0%",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn validates_names() {
+        assert_eq!(get_baseline_path("before-fix_2").is_ok(), true);
+        assert_eq!(get_baseline_path("").is_err(), true);
+        assert_eq!(get_baseline_path("../timings").is_err(), true);
+        assert_eq!(get_baseline_path(".hidden").is_err(), true);
+    }
+}
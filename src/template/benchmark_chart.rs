@@ -0,0 +1,232 @@
+/// Module that draws the timings as an SVG bar chart, to be embedded in the readme.
+///
+/// The chart has one group of bars per day, one bar per part, on a logarithmic scale
+/// so that parts running in nanoseconds remain visible next to the ones running in seconds.
+use std::{fmt::Write, fs, io, path::PathBuf, time::Duration};
+
+use super::{
+    timings::{StepTiming, Timing, Timings},
+    Year,
+};
+
+/// The chart of the current year, `.assets/benchmarks-<year>.svg` for the years other than the default one.
+fn get_chart_path() -> PathBuf {
+    match Year::current().filter(|year| !year.is_default()) {
+        Some(year) => PathBuf::from(format!(".assets/benchmarks-{year}.svg")),
+        None => PathBuf::from(".assets/benchmarks.svg"),
+    }
+}
+
+// leaves room for the title and the legend when there are only a few days.
+const MIN_WIDTH: f64 = 480.0;
+const HEIGHT: f64 = 320.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 40.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const GROUP_WIDTH: f64 = 36.0;
+const BAR_WIDTH: f64 = 14.0;
+
+const PARTS: [(&str, &str); 2] = [("Part 1", "#4e79a7"), ("Part 2", "#f28e2b")];
+
+/// The decades spanned by the vertical axis, as powers of ten of nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Scale {
+    low: i32,
+    high: i32,
+}
+
+impl Scale {
+    fn new(nanos: impl Iterator<Item = f64>) -> Self {
+        let (min, max) = nanos
+            .filter(|x| *x > 0.0)
+            .fold((f64::INFINITY, 0_f64), |(min, max), x| {
+                (min.min(x), max.max(x))
+            });
+
+        if max == 0.0 {
+            return Scale { low: 0, high: 1 };
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let (low, high) = (min.log10().floor() as i32, max.log10().ceil() as i32);
+        Scale {
+            low,
+            high: high.max(low + 1),
+        }
+    }
+
+    /// The vertical position of a duration, in pixels.
+    fn y(self, nanos: f64) -> f64 {
+        let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        let ratio = (nanos.log10() - f64::from(self.low)) / f64::from(self.high - self.low);
+        MARGIN_TOP + plot_height * (1.0 - ratio.clamp(0.0, 1.0))
+    }
+}
+
+/// Renders the chart of the timings.
+fn render(timings: &Timings) -> String {
+    let scale = Scale::new(
+        timings
+            .data
+            .iter()
+            .flat_map(parts)
+            .flatten()
+            .map(|step| step.nanos),
+    );
+
+    #[allow(clippy::cast_precision_loss)]
+    let width =
+        (MARGIN_LEFT + GROUP_WIDTH * timings.data.len() as f64 + MARGIN_RIGHT).max(MIN_WIDTH);
+    let bottom = HEIGHT - MARGIN_BOTTOM;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{HEIGHT}" viewBox="0 0 {width} {HEIGHT}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#ffffff"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{MARGIN_LEFT}" y="20" font-size="14" font-weight="bold">Benchmarks (log scale, total {:.2}ms)</text>"#,
+        timings.total_millis()
+    );
+
+    // legend.
+    for (i, (label, color)) in PARTS.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let x = width - MARGIN_RIGHT - 130.0 + 65.0 * i as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="11" width="10" height="10" fill="{color}"/><text x="{}" y="20">{label}</text>"#,
+            x + 14.0
+        );
+    }
+
+    // one gridline per decade.
+    for decade in scale.low..=scale.high {
+        let nanos = 10_f64.powi(decade);
+        let y = scale.y(nanos);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let label = format!("{:?}", Duration::from_nanos(nanos as u64));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{MARGIN_LEFT}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="#dddddd"/><text x="{}" y="{:.1}" text-anchor="end">{label}</text>"##,
+            width - MARGIN_RIGHT,
+            MARGIN_LEFT - 6.0,
+            y + 4.0
+        );
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let group_x = MARGIN_LEFT + GROUP_WIDTH * i as f64;
+
+        for (j, (step, (label, color))) in parts(timing).into_iter().zip(PARTS).enumerate() {
+            let Some(step) = step else {
+                continue;
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let x = group_x + (GROUP_WIDTH - 2.0 * BAR_WIDTH) / 2.0 + BAR_WIDTH * j as f64;
+            let y = scale.y(step.nanos);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{x:.1}" y="{y:.1}" width="{BAR_WIDTH}" height="{:.1}" fill="{color}"><title>Day {} {label}: {:.1?}</title></rect>"#,
+                bottom - y,
+                timing.day,
+                step.duration()
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            group_x + GROUP_WIDTH / 2.0,
+            bottom + 16.0,
+            timing.day.into_inner()
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        r##"<line x1="{MARGIN_LEFT}" y1="{bottom}" x2="{}" y2="{bottom}" stroke="#333333"/>"##,
+        width - MARGIN_RIGHT
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn parts(timing: &Timing) -> [Option<&StepTiming>; 2] {
+    [timing.part_1.as_ref(), timing.part_2.as_ref()]
+}
+
+/// Writes the chart of the timings to `.assets/benchmarks.svg`, see [`get_chart_path`].
+pub fn update(timings: &Timings) -> Result<(), io::Error> {
+    let path = get_chart_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, render(timings))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{render, Scale};
+    use crate::{
+        day,
+        template::timings::{StepTiming, Timing, Timings},
+    };
+
+    fn step(nanos: u64) -> Option<StepTiming> {
+        Some(StepTiming::from_duration(Duration::from_nanos(nanos)))
+    }
+
+    #[test]
+    fn spans_whole_decades() {
+        let scale = Scale::new([150.0, 2_000_000.0].into_iter());
+        assert_eq!(scale, Scale { low: 2, high: 7 });
+        assert_eq!(scale.y(100.0), 280.0);
+        assert_eq!(scale.y(10_000_000.0), 40.0);
+
+        assert_eq!(Scale::new([100.0].into_iter()), Scale { low: 2, high: 3 });
+        assert_eq!(Scale::new(std::iter::empty()), Scale { low: 0, high: 1 });
+    }
+
+    #[test]
+    fn renders_a_bar_per_part() {
+        let timing = |day, part_1, part_2| Timing {
+            part_1,
+            part_2,
+            ..Timing::new(day)
+        };
+        let timings = Timings {
+            data: vec![
+                timing(day!(1), step(40_600), step(177_900)),
+                timing(day!(12), step(4_700_000), None),
+            ],
+            history: vec![],
+        };
+
+        let svg = render(&timings);
+        assert_eq!(svg.starts_with("<svg"), true);
+        assert_eq!(svg.ends_with("</svg>\n"), true);
+        assert_eq!(svg.matches("<title>").count(), 3);
+        assert_eq!(svg.contains("<title>Day 01 Part 1: 40.6µs</title>"), true);
+        assert_eq!(svg.contains("<title>Day 12 Part 2"), false);
+        assert_eq!(svg.contains(">10ms</text>"), true);
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(&Timings::default());
+        assert_eq!(svg.matches("<title>").count(), 0);
+    }
+}
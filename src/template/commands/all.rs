@@ -1,5 +1,35 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{collections::HashSet, process, time::Duration};
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    run_multi(all_days().collect(), is_release, is_timed);
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::{all_days, Day};
+
+pub fn handle(
+    days: Vec<Day>,
+    is_release: bool,
+    is_timed: bool,
+    is_isolated: bool,
+    timeout: Option<Duration>,
+    jobs: Option<usize>,
+) {
+    // NOTE: run every day when none are specified.
+    let days_to_run: HashSet<Day> = if days.is_empty() {
+        all_days().collect()
+    } else {
+        days.into_iter().collect()
+    };
+
+    let run = run_multi(
+        days_to_run,
+        RunOptions {
+            is_release,
+            is_timed,
+            is_isolated,
+            timeout,
+            jobs,
+        },
+    );
+
+    if !run.mismatches.is_empty() {
+        process::exit(1);
+    }
 }
@@ -1,14 +1,40 @@
-use crate::template::{aoc_cli, Day};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::downloads::{self, Source};
+use crate::template::Day;
+
+pub fn handle(day: Day, force: bool) {
+    let client = AocClient::from_env().unwrap_or_else(|e| {
+        eprintln!("{e}");
         process::exit(1);
-    }
+    });
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = download(&client, day, force) {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
 }
+
+fn download(client: &AocClient, day: Day, force: bool) -> Result<(), AocClientError> {
+    let input_path = downloads::get_input_path(day);
+    let puzzle_path = downloads::get_puzzle_path(day);
+
+    let input = downloads::input(client, day, force)?;
+    let (_, puzzle) = downloads::puzzle(client, day, force)?;
+
+    println!("---");
+    match input {
+        Source::Downloaded => println!("🎄 Successfully wrote input to \"{input_path}\"."),
+        Source::Cached => {
+            println!("🎄 Kept existing input \"{input_path}\", use --force to download it again.")
+        }
+    }
+    match puzzle {
+        Source::Downloaded => println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\"."),
+        Source::Cached => {
+            println!("🎄 Kept existing puzzle \"{puzzle_path}\", it already has both parts.")
+        }
+    }
+    Ok(())
+}
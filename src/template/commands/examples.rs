@@ -0,0 +1,40 @@
+use std::{fs, process};
+
+use crate::template::downloads::get_puzzle_path;
+use crate::template::examples::{self, get_example_path};
+use crate::template::Day;
+
+pub fn handle(day: Day, force: bool) {
+    let puzzle_path = get_puzzle_path(day);
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Could not read \"{puzzle_path}\". Try running \"cargo download {day}\" first.");
+        process::exit(1);
+    };
+
+    let examples = examples::extract(&puzzle);
+    if examples.is_empty() {
+        println!("No examples found in \"{puzzle_path}\".");
+        return;
+    }
+
+    for (index, example) in examples.iter().enumerate() {
+        let index = index + 1;
+        let path = get_example_path(day, index);
+
+        match examples::write(day, index, example, force) {
+            Ok(true) => match example.format_answers().join(", ") {
+                answers if answers.is_empty() => {
+                    println!("Created example file \"{path}\"");
+                }
+                answers => println!("Created example file \"{path}\" ({answers})"),
+            },
+            Ok(false) => {
+                println!("Kept existing example file \"{path}\", use --force to overwrite it.");
+            }
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::template::report::{self, DEFAULT_REPORT_PATH};
+use crate::template::timings::Timings;
+use crate::template::Year;
+
+pub fn handle(output: Option<String>) {
+    let path = output.as_deref().unwrap_or(DEFAULT_REPORT_PATH);
+    let timings = Timings::read_from_file();
+
+    match report::write(path, Year::current().map(Year::into_inner), &timings) {
+        Ok(()) => {
+            println!("Stored report in \"{path}\".")
+        }
+        Err(e) => {
+            eprintln!("Failed to store report: {e}");
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
 
-use crate::template::Day;
+use crate::template::downloads::get_puzzle_path;
+use crate::template::examples::{self, Example};
+use crate::template::{data_dir, get_path_for_bin, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -17,10 +19,41 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create(true).open(path)
 }
 
+/// Fills the tests of a part with the first example that has a numeric answer, if the puzzle has been downloaded.
+/// The tests of a year other than the default one read the examples of that year.
+fn fill_test(module: &str, examples: &[Example], part: u8, year: Option<Year>) -> String {
+    let (example, answer) = match examples::get_test_case(examples, part) {
+        Some((index, answer)) => (
+            format!("read_file_part(\"examples\", DAY, {index})"),
+            format!("Some({answer})"),
+        ),
+        None => ("read_file(\"examples\", DAY)".into(), "None".into()),
+    };
+    let example = match year {
+        Some(year) => format!("Year::new({year}).unwrap().{example}"),
+        None => example,
+    };
+
+    module
+        .replace(&format!("%PART_{part}_EXAMPLE%"), &example)
+        .replace(&format!("%PART_{part}_ANSWER%"), &answer)
+}
+
 pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let data_dir = data_dir();
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let input_path = input_path.to_string_lossy();
+    let example_path = data_dir.join("examples").join(format!("{day}.txt"));
+    let example_path = example_path.to_string_lossy();
+    let module_path = get_path_for_bin(day);
+    let year = Year::current().filter(|year| !year.is_default());
+
+    for dir in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(data_dir.join(dir)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -30,11 +63,15 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    let examples = fs::read_to_string(get_puzzle_path(day))
+        .map(|puzzle| examples::extract(&puzzle))
+        .unwrap_or_default();
+
+    let module = MODULE_TEMPLATE.replace("%DAY_NUMBER%", &day.into_inner().to_string());
+    let module = fill_test(&module, &examples, 1, year);
+    let module = fill_test(&module, &examples, 2, year);
+
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -44,6 +81,24 @@ pub fn handle(day: Day) {
         }
     }
 
+    // NOTE: the tests of the module read the examples, which are only written by `cargo examples` otherwise.
+    for (index, example) in examples.iter().enumerate() {
+        let index = index + 1;
+        match examples::write(day, index, example, false) {
+            Ok(true) => {
+                println!(
+                    "Created example file \"{}\"",
+                    examples::get_example_path(day, index)
+                );
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
@@ -65,5 +120,8 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    match year {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {}` to run your solution.", day),
+    }
 }
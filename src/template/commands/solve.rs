@@ -1,9 +1,9 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{bin_name, input::InputSource, Day};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>, input: InputSource) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name(day)];
 
     if release {
         cmd_args.push("--release".to_string());
@@ -20,6 +20,8 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    cmd_args.extend(input.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -0,0 +1,16 @@
+use crate::template::readme_benchmarks::Error;
+use crate::template::readme_stars;
+
+pub fn handle() {
+    match readme_stars::update() {
+        Ok(()) => {
+            println!("Stored updated stars.")
+        }
+        Err(Error::Parser(reason)) => {
+            eprintln!("Failed to store updated stars: {reason}");
+        }
+        Err(_) => {
+            eprintln!("Failed to store updated stars.");
+        }
+    }
+}
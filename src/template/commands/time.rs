@@ -1,12 +1,47 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process, time::Duration};
 
-use crate::template::run_multi::run_multi;
+use crate::template::export::{self, ExportFormat};
+use crate::template::history::{self, Measurement};
+use crate::template::run_multi::{run_multi, RunOptions};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, baseline, benchmark_chart, readme_benchmarks, Day, ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, recreate_all: bool, update_readme: bool) {
+/// The options of the `time` command.
+pub struct TimeOptions {
+    pub recreate_all: bool,
+    pub update_readme: bool,
+    /// Draws the timings in `.assets/benchmarks.svg`.
+    pub update_chart: bool,
+    pub is_isolated: bool,
+    pub timeout: Option<Duration>,
+    pub jobs: Option<usize>,
+    pub regression_threshold: f64,
+    /// Stores the timings of this run as a named baseline.
+    pub save_baseline: Option<String>,
+    /// Compares the timings of this run with a named baseline.
+    pub baseline: Option<String>,
+    /// Writes all the stored timings to a file in another format.
+    pub export: Option<(ExportFormat, String)>,
+}
+
+pub fn handle(day: Option<Day>, options: TimeOptions) {
     let stored_timings = Timings::read_from_file();
 
+    // read the baseline first, so a typo in its name doesn't waste a whole run.
+    let baseline = options.baseline.as_deref().map(|name| {
+        let timings = baseline::load(name).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+        (name, timings)
+    });
+
+    // NOTE: a baseline holds the timings of this run only, so every day runs for it to be complete.
+    let recreate_all =
+        options.recreate_all || options.save_baseline.is_some() || options.baseline.is_some();
+
     let days_to_run = day.map(|day| HashSet::from([day])).unwrap_or_else(|| {
         if recreate_all {
             all_days().collect()
@@ -18,12 +53,88 @@ pub fn handle(day: Option<Day>, recreate_all: bool, update_readme: bool) {
         }
     });
 
-    let timings = run_multi(days_to_run, true, true).unwrap();
+    if options.timeout.is_some() && !options.is_isolated {
+        println!("A timeout is set, each day runs in its own binary so a timed out step cannot skew the next ones.");
+    }
+
+    let mut timings = run_multi(
+        days_to_run,
+        RunOptions {
+            is_release: true,
+            is_timed: true,
+            is_isolated: options.is_isolated,
+            timeout: options.timeout,
+            jobs: options.jobs,
+        },
+    )
+    .timings
+    .unwrap();
+
+    if let Some((name, baseline)) = &baseline {
+        let comparisons = baseline::compare(baseline, &timings);
+        println!("\n{ANSI_BOLD}Compared to baseline `{name}`:{ANSI_RESET}");
+        print!("{}", baseline::format_table(name, &comparisons));
+    }
+
+    if let Some(name) = &options.save_baseline {
+        match baseline::save(name, &timings) {
+            Ok(()) => println!("\nSaved baseline `{name}`."),
+            Err(e) => eprintln!("\nFailed to save baseline `{name}`: {e}"),
+        }
+    }
+
+    let timestamp = history::now();
+    let commit = history::read_git_commit();
+    timings.history = timings
+        .data
+        .iter()
+        .flat_map(|timing| Measurement::from_timing(timing, timestamp, commit.as_deref()))
+        .collect();
+
+    let regressions = history::find_regressions(
+        &stored_timings.history,
+        &timings.history,
+        options.regression_threshold,
+    );
+
+    if !regressions.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}Regressions (> {}%):{ANSI_RESET}",
+            options.regression_threshold
+        );
+        for regression in &regressions {
+            println!("{regression}");
+        }
+    }
 
     let merged_timings = stored_timings.merge(&timings);
     merged_timings.store_file().unwrap();
 
-    if update_readme {
+    if let Some((format, path)) = &options.export {
+        println!();
+        match export::export(&merged_timings, *format, path) {
+            Ok(()) => {
+                println!("Exported timings to \"{path}\".")
+            }
+            Err(e) => {
+                eprintln!("Failed to export timings: {e}");
+            }
+        }
+    }
+
+    if options.update_chart {
+        println!();
+        match benchmark_chart::update(&merged_timings) {
+            Ok(()) => {
+                println!("Stored updated benchmark chart.")
+            }
+            Err(_) => {
+                eprintln!("Failed to store updated benchmark chart.");
+            }
+        }
+    }
+
+    if options.update_readme {
         println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
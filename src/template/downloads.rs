@@ -0,0 +1,85 @@
+/// Module that stores the downloaded inputs and puzzle descriptions, and only downloads them again when needed:
+///  - an input never changes, it is only downloaded if there is none yet.
+///  - a description only changes once, when the second part is unlocked.
+///
+/// Both are downloaded again with `force`.
+use std::{fs, path::Path};
+
+use super::aoc_client::{AocClient, AocClientError};
+use super::{data_dir, Day};
+
+/// Where a file comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Downloaded,
+    Cached,
+}
+
+pub fn get_input_path(day: Day) -> String {
+    format!("{}/inputs/{day}.txt", data_dir().display())
+}
+
+pub fn get_puzzle_path(day: Day) -> String {
+    format!("{}/puzzles/{day}.md", data_dir().display())
+}
+
+/// Downloads the input of a day, unless it has already been.
+pub fn input(client: &AocClient, day: Day, force: bool) -> Result<Source, AocClientError> {
+    let path = get_input_path(day);
+
+    // NOTE: `cargo scaffold` creates an empty input file.
+    let is_cached = fs::read_to_string(&path).is_ok_and(|input| !input.trim().is_empty());
+    if is_cached && !force {
+        return Ok(Source::Cached);
+    }
+
+    write_file(&path, &client.get_input(day)?)?;
+    Ok(Source::Downloaded)
+}
+
+/// Returns the description of a day, downloading it unless it already has both parts.
+pub fn puzzle(
+    client: &AocClient,
+    day: Day,
+    force: bool,
+) -> Result<(String, Source), AocClientError> {
+    let path = get_puzzle_path(day);
+
+    if let Ok(puzzle) = fs::read_to_string(&path) {
+        if has_part_two(&puzzle) && !force {
+            return Ok((puzzle, Source::Cached));
+        }
+    }
+
+    let puzzle = client.get_puzzle(day)?;
+    write_file(&path, &puzzle)?;
+    Ok((puzzle, Source::Downloaded))
+}
+
+fn has_part_two(puzzle: &str) -> bool {
+    puzzle.contains("--- Part Two ---")
+}
+
+fn write_file(path: &str, content: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::has_part_two;
+
+    #[test]
+    fn detects_part_two() {
+        assert_eq!(has_part_two("## --- Day 1: Trebuchet?! ---\n\n..."), false);
+        assert_eq!(
+            has_part_two("## --- Day 1: Trebuchet?! ---\n\n...\n\n## --- Part Two ---\n\n..."),
+            true
+        );
+    }
+}
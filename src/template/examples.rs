@@ -0,0 +1,187 @@
+/// Module that extracts the examples of a puzzle from its description, see [`super::aoc_client::to_markdown`].
+///
+/// The code blocks of the description are the example inputs. The emphasized code spans that follow a block,
+/// e.g. *`142`*, are its expected answers: the last one of each part is kept, as the earlier ones
+/// usually are intermediate results.
+use std::{fs, io, path::Path};
+
+use super::{data_dir, Day};
+
+/// An example input and its expected answers, one per part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: [Option<String>; 2],
+}
+
+impl Example {
+    /// The expected answers, e.g. `part 1: 142`.
+    pub fn format_answers(&self) -> Vec<String> {
+        self.answers
+            .iter()
+            .enumerate()
+            .filter_map(|(i, answer)| Some(format!("part {}: {}", i + 1, answer.as_ref()?)))
+            .collect()
+    }
+}
+
+/// Extracts the examples of a puzzle description, in order.
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let mut part = 1;
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim() == "```" {
+            match block.take() {
+                Some(input) => examples.push(Example {
+                    input,
+                    answers: [None, None],
+                }),
+                None => block = Some(String::new()),
+            }
+            continue;
+        }
+
+        if let Some(input) = &mut block {
+            input.push_str(line);
+            input.push('\n');
+            continue;
+        }
+
+        if line.contains("--- Part Two ---") {
+            part = 2;
+        }
+
+        // NOTE: the answers of the second part usually refer to the examples of the first one.
+        if let Some(example) = examples.last_mut() {
+            if let Some(answer) = get_emphasized_code(line).last() {
+                example.answers[part - 1] = Some((*answer).to_owned());
+            }
+        }
+    }
+
+    examples
+}
+
+/// The emphasized code spans of a line, e.g. `142` in "for a total of *`142`*".
+fn get_emphasized_code(line: &str) -> Vec<&str> {
+    let mut spans = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("*`") {
+        let Some(end) = rest[start + 2..].find("`*") else {
+            break;
+        };
+        spans.push(&rest[start + 2..start + 2 + end]);
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    spans
+}
+
+/// The first example of a part with a numeric answer, as its 1-based index and the answer.
+/// This is the one the scaffolded tests assert on.
+pub fn get_test_case(examples: &[Example], part: u8) -> Option<(usize, &str)> {
+    examples.iter().enumerate().find_map(|(index, example)| {
+        let answer = example.answers[usize::from(part - 1)].as_deref()?;
+        answer.parse::<u64>().is_ok().then_some((index + 1, answer))
+    })
+}
+
+pub fn get_example_path(day: Day, index: usize) -> String {
+    format!("{}/examples/{day}-{index}.txt", data_dir().display())
+}
+
+pub fn get_answers_path(day: Day, index: usize) -> String {
+    format!("{}/examples/{day}-{index}.answers", data_dir().display())
+}
+
+/// Writes an example to `data/examples/<day>-<index>.txt`, and its answers to `data/examples/<day>-<index>.answers`.
+/// Returns `false` if the example exists and is not overwritten.
+pub fn write(day: Day, index: usize, example: &Example, force: bool) -> Result<bool, io::Error> {
+    let path = get_example_path(day, index);
+    if Path::new(&path).exists() && !force {
+        return Ok(false);
+    }
+
+    fs::create_dir_all(data_dir().join("examples"))?;
+    fs::write(&path, &example.input)?;
+
+    let answers: String = example
+        .format_answers()
+        .iter()
+        .map(|answer| format!("{answer}\n"))
+        .collect();
+    fs::write(get_answers_path(day, index), answers)?;
+
+    Ok(true)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, get_emphasized_code, get_test_case, Example};
+
+    fn get_mock_puzzle() -> String {
+        [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "The calibration values are `12` and *`38`*. Adding these together produces *`50`*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Using the same example, the sum is *`77`*. Another example:",
+            "",
+            "```",
+            "two1nine",
+            "```",
+            "",
+            "The value is *`ABC`*.",
+        ]
+        .join("\n")
+    }
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            extract(&get_mock_puzzle()),
+            [
+                Example {
+                    input: "1abc2\npqr3stu8vwx\n".into(),
+                    answers: [Some("50".into()), Some("77".into())],
+                },
+                Example {
+                    input: "two1nine\n".into(),
+                    answers: [None, Some("ABC".into())],
+                },
+            ]
+        );
+        assert_eq!(extract("## --- Day 1: Trebuchet?! ---\n\n*`1`*"), []);
+    }
+
+    #[test]
+    fn finds_emphasized_code() {
+        assert_eq!(get_emphasized_code("a *`1`* b `2` c *`3`*"), ["1", "3"]);
+        assert_eq!(
+            get_emphasized_code("*wrong* and *`unclosed"),
+            Vec::<&str>::new()
+        );
+    }
+
+    #[test]
+    fn finds_test_cases() {
+        let examples = extract(&get_mock_puzzle());
+        assert_eq!(get_test_case(&examples, 1), Some((1, "50")));
+        assert_eq!(get_test_case(&examples, 2), Some((1, "77")));
+        assert_eq!(get_test_case(&examples[1..], 2), None);
+    }
+}
@@ -0,0 +1,246 @@
+/// Module that exports the timings to formats other tools understand.
+///
+/// - `csv`: one row per step, for spreadsheets.
+/// - `json`: one `benchmark-complete` message per line, as printed by `cargo criterion --message-format=json`.
+///   Only the summary of the samples is stored, so the confidence bounds equal the estimates
+///   and the raw samples are left empty.
+/// - `markdown`: the benchmark table of the readme.
+use std::{collections::HashMap, fs, io, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use super::protocol::{Status, Step};
+use super::readme_benchmarks::{construct_table, TableOptions};
+use super::stats::Summary;
+use super::timings::{StepTiming, Timing, Timings};
+
+/// The formats the timings can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected `csv`, `json` or `markdown`."
+            )),
+        }
+    }
+}
+
+/// Writes the timings to a file in the given format.
+pub fn export(timings: &Timings, format: ExportFormat, path: &str) -> Result<(), io::Error> {
+    let content = match format {
+        ExportFormat::Csv => to_csv(timings),
+        ExportFormat::Json => to_criterion_json(timings),
+        ExportFormat::Markdown => {
+            let options = TableOptions::from_env().unwrap_or_default();
+            let table = construct_table("#", timings.clone(), timings.total_millis(), &options);
+            format!("{table}\n")
+        }
+    };
+    fs::write(path, content)
+}
+
+fn steps(timing: &Timing) -> [(Step, Option<&StepTiming>, Option<Status>); 3] {
+    [
+        (Step::Parse, timing.parse.as_ref(), timing.parse_status),
+        (Step::Part(1), timing.part_1.as_ref(), timing.part_1_status),
+        (Step::Part(2), timing.part_2.as_ref(), timing.part_2_status),
+    ]
+}
+
+/* -------------------------------------------------------------------------- */
+
+const CSV_HEADER: &str = "day,step,status,median_ns,mean_ns,min_ns,max_ns,std_dev_ns,p95_ns,samples,outliers,peak_bytes,allocations";
+
+/// One row per step that ran, the columns a step doesn't have are left empty.
+fn to_csv(timings: &Timings) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+
+    for timing in &timings.data {
+        for (step, step_timing, status) in steps(timing) {
+            if step_timing.is_none() && status.is_none() {
+                continue;
+            }
+
+            let stats = step_timing.and_then(|s| s.stats);
+            let memory = step_timing.and_then(|s| s.memory);
+            let nanos = |f: fn(&Summary) -> Duration| {
+                stats.map_or(String::new(), |s| f(&s).as_nanos().to_string())
+            };
+
+            let row = [
+                timing.day.to_string(),
+                step.to_string(),
+                status.map_or(String::new(), |s| s.as_str().into()),
+                step_timing.map_or(String::new(), |s| s.nanos.to_string()),
+                nanos(|s| s.mean),
+                nanos(|s| s.min),
+                nanos(|s| s.max),
+                nanos(|s| s.std_dev),
+                nanos(|s| s.p95),
+                step_timing.map_or(String::new(), |s| s.samples.to_string()),
+                stats.map_or(String::new(), |s| s.outliers.to_string()),
+                memory.map_or(String::new(), |m| m.peak_bytes.to_string()),
+                memory.map_or(String::new(), |m| m.allocations.to_string()),
+            ];
+            lines.push(row.join(","));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn estimate(nanos: f64) -> JsonValue {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("estimate".into(), JsonValue::Number(nanos));
+    map.insert("lower_bound".into(), JsonValue::Number(nanos));
+    map.insert("upper_bound".into(), JsonValue::Number(nanos));
+    map.insert("unit".into(), JsonValue::String("ns".into()));
+    JsonValue::Object(map)
+}
+
+fn benchmark_complete(id: String, step: &StepTiming) -> JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    let mean = step
+        .stats
+        .map_or(step.nanos, |stats| stats.mean.as_nanos() as f64);
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "reason".into(),
+        JsonValue::String("benchmark-complete".into()),
+    );
+    map.insert("id".into(), JsonValue::String(id));
+    map.insert("report_directory".into(), JsonValue::String(String::new()));
+    map.insert("iteration_count".into(), JsonValue::Array(vec![]));
+    map.insert("measured_values".into(), JsonValue::Array(vec![]));
+    map.insert("unit".into(), JsonValue::String("ns".into()));
+    map.insert("throughput".into(), JsonValue::Array(vec![]));
+    map.insert("typical".into(), estimate(mean));
+    map.insert("mean".into(), estimate(mean));
+    map.insert("median".into(), estimate(step.nanos));
+    // NOTE: the median absolute deviation is not measured, but the field is required.
+    map.insert("median_abs_dev".into(), estimate(0.0));
+    map.insert("slope".into(), JsonValue::Null);
+    map.insert("change".into(), JsonValue::Null);
+    JsonValue::Object(map)
+}
+
+/// One message per timed step, identified as `Day NN/Part N`.
+fn to_criterion_json(timings: &Timings) -> String {
+    timings
+        .data
+        .iter()
+        .flat_map(|timing| {
+            steps(timing)
+                .into_iter()
+                .filter_map(|(step, step_timing, _)| {
+                    let id = format!("Day {}/{step}", timing.day);
+                    benchmark_complete(id, step_timing?).stringify().ok()
+                })
+                .map(|line| line + "\n")
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, str::FromStr, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::{to_criterion_json, to_csv, ExportFormat, CSV_HEADER};
+    use crate::{
+        day,
+        template::{
+            protocol::Status,
+            timings::{StepTiming, Timing, Timings},
+        },
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![Timing {
+                part_1: Some(StepTiming::from_duration(Duration::from_micros(40))),
+                part_1_status: Some(Status::Ok),
+                part_2_status: Some(Status::Panicked),
+                total_nanos: 40_000.0,
+                ..Timing::new(day!(1))
+            }],
+            history: vec![],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(ExportFormat::from_str("csv"), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::from_str("md"), Ok(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::from_str("xml").is_err(), true);
+    }
+
+    #[test]
+    fn exports_csv() {
+        assert_eq!(
+            to_csv(&get_mock_timings()),
+            [
+                CSV_HEADER,
+                "01,Part 1,ok,40000,40000,40000,40000,0,40000,1,0,,",
+                "01,Part 2,panicked,,,,,,,,,,",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn exports_criterion_messages() {
+        let json = to_criterion_json(&get_mock_timings());
+        let lines: Vec<_> = json.lines().collect();
+        assert_eq!(lines.len(), 1);
+
+        let message: JsonValue = lines[0].parse().unwrap();
+        let message = message.get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(
+            message["reason"],
+            JsonValue::String("benchmark-complete".into())
+        );
+        assert_eq!(message["id"], JsonValue::String("Day 01/Part 1".into()));
+        assert_eq!(message["median"]["estimate"], JsonValue::Number(40_000.0));
+
+        for key in [
+            "report_directory",
+            "iteration_count",
+            "measured_values",
+            "unit",
+            "throughput",
+            "slope",
+            "change",
+        ] {
+            assert_eq!(message.contains_key(key), true, "missing `{key}`");
+        }
+
+        for key in ["typical", "mean", "median", "median_abs_dev"] {
+            let estimate = message[key].get::<HashMap<String, JsonValue>>().unwrap();
+            for field in ["estimate", "lower_bound", "upper_bound"] {
+                assert_eq!(estimate[field].is_number(), true, "`{key}.{field}`");
+            }
+            assert_eq!(estimate["unit"], JsonValue::String("ns".into()));
+        }
+    }
+}
@@ -0,0 +1,314 @@
+/// Module that keeps the history of the benchmarks, to detect performance regressions.
+///
+/// Every `cargo time` run appends one measurement per timed step to `timings.json`,
+/// tagged with the date and, if available, the hash of the checked out git commit.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{protocol::Step, timings::Timing, Day};
+
+/// By default, a step is flagged when it gets 10% slower than its previous best.
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+/// A single measurement of a step, as stored in the history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub day: Day,
+    pub step: Step,
+    /// Seconds since the UNIX epoch.
+    pub timestamp: u64,
+    /// The git commit that was checked out, if any.
+    pub commit: Option<String>,
+    /// The median duration of the step, in nanoseconds.
+    pub nanos: f64,
+}
+
+impl Measurement {
+    /// Creates the measurements of the timed steps of a day.
+    pub fn from_timing(timing: &Timing, timestamp: u64, commit: Option<&str>) -> Vec<Self> {
+        [Step::Parse, Step::Part(1), Step::Part(2)]
+            .into_iter()
+            .filter_map(|step| {
+                Some(Measurement {
+                    day: timing.day,
+                    step,
+                    timestamp,
+                    commit: commit.map(Into::into),
+                    nanos: timing.step(step)?.nanos,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A step that got slower than its previous best.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    pub best: Measurement,
+    pub new: Measurement,
+}
+
+impl Regression {
+    /// How much slower the new measurement is, in percent.
+    pub fn percentage(&self) -> f64 {
+        (self.new.nanos / self.best.nanos - 1.0) * 100.0
+    }
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = |m: &Measurement| Duration::from_nanos(m.nanos as u64);
+
+        write!(
+            f,
+            "Day {} {}: {:.1?} → {:.1?} (+{:.1}%, best",
+            self.new.day,
+            self.new.step,
+            duration(&self.best),
+            duration(&self.new),
+            self.percentage()
+        )?;
+        if let Some(commit) = &self.best.commit {
+            write!(f, " at {}", &commit[..commit.len().min(7)])?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Finds the new measurements that are more than `threshold` percent slower than the best one in the history.
+pub fn find_regressions(
+    history: &[Measurement],
+    new: &[Measurement],
+    threshold: f64,
+) -> Vec<Regression> {
+    new.iter()
+        .filter_map(|new| {
+            let best = history
+                .iter()
+                .filter(|m| m.day == new.day && m.step == new.step)
+                .min_by(|a, b| a.nanos.total_cmp(&b.nanos))?;
+
+            let regression = Regression {
+                best: best.clone(),
+                new: new.clone(),
+            };
+            (regression.percentage() > threshold).then_some(regression)
+        })
+        .collect()
+}
+
+/// Parses a percentage such as `10` or `12.5%`.
+pub fn parse_percentage(s: &str) -> Result<f64, String> {
+    s.trim()
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .ok()
+        .filter(|x| x.is_finite() && *x >= 0.0)
+        .ok_or(format!("invalid percentage `{s}`, expected e.g. `10%`."))
+}
+
+/// Seconds since the UNIX epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Reads the hash of the checked out git commit from `.git`, without calling git.
+pub fn read_git_commit() -> Option<String> {
+    let git_dir = Path::new(".git");
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let loose_ref = |reference: &str| fs::read_to_string(git_dir.join(reference)).ok();
+    let packed_refs = fs::read_to_string(git_dir.join("packed-refs")).unwrap_or_default();
+    resolve_head(&head, loose_ref, &packed_refs)
+}
+
+/// Resolves the content of `.git/HEAD` to a commit hash.
+/// The branch `HEAD` points to is either stored in its own file, or in `.git/packed-refs`.
+fn resolve_head(
+    head: &str,
+    loose_ref: impl Fn(&str) -> Option<String>,
+    packed_refs: &str,
+) -> Option<String> {
+    let head = head.trim();
+
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        // detached HEAD.
+        return Some(head.to_owned());
+    };
+
+    if let Some(hash) = loose_ref(reference) {
+        return Some(hash.trim().to_owned());
+    }
+
+    packed_refs.lines().find_map(|line| {
+        let (hash, name) = line.split_once(' ')?;
+        (name == reference).then(|| hash.to_owned())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Measurement> for JsonValue {
+    fn from(value: &Measurement) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::from(value.step));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        if let Some(commit) = &value.commit {
+            map.insert("commit".into(), JsonValue::String(commit.clone()));
+        }
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Measurement {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected measurement to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected measurement.day to be a Day struct.")?;
+
+        let step = json
+            .get("part")
+            .ok_or("Expected measurement.part to be present.")
+            .map(Step::try_from)??;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected measurement.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .filter(|v| !v.is_null())
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected measurement.commit to be a string.")
+            })
+            .transpose()?
+            .cloned();
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected measurement.nanos to be a number.")?;
+
+        Ok(Measurement {
+            day,
+            step,
+            timestamp,
+            commit,
+            nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{find_regressions, parse_percentage, resolve_head, Measurement};
+    use crate::{day, template::protocol::Step};
+
+    fn measurement(step: Step, timestamp: u64, nanos: f64) -> Measurement {
+        Measurement {
+            day: day!(1),
+            step,
+            timestamp,
+            commit: Some("0123456789abcdef".into()),
+            nanos,
+        }
+    }
+
+    #[test]
+    fn finds_regressions() {
+        let history = [
+            measurement(Step::Part(1), 1, 1000.0),
+            measurement(Step::Part(1), 2, 800.0),
+            measurement(Step::Part(2), 2, 1000.0),
+        ];
+        let new = [
+            measurement(Step::Parse, 3, 5000.0),
+            measurement(Step::Part(1), 3, 900.0),
+            measurement(Step::Part(2), 3, 1050.0),
+        ];
+
+        let regressions = find_regressions(&history, &new, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].best.nanos, 800.0);
+        assert_eq!(regressions[0].new.nanos, 900.0);
+        assert_eq!(
+            regressions[0].to_string(),
+            "Day 01 Part 1: 800.0ns → 900.0ns (+12.5%, best at 0123456)"
+        );
+    }
+
+    #[test]
+    fn parses_percentages() {
+        assert_eq!(parse_percentage("10"), Ok(10.0));
+        assert_eq!(parse_percentage("12.5%"), Ok(12.5));
+        assert_eq!(parse_percentage("-5").is_err(), true);
+        assert_eq!(parse_percentage("ten").is_err(), true);
+    }
+
+    #[test]
+    fn resolves_git_head() {
+        let packed_refs =
+            "# pack-refs with: peeled fully-peeled sorted\nabc123 refs/heads/packed\n";
+        let loose_ref =
+            |reference: &str| (reference == "refs/heads/main").then(|| "def456\n".into());
+
+        assert_eq!(
+            resolve_head("ref: refs/heads/main\n", loose_ref, packed_refs),
+            Some("def456".into())
+        );
+        assert_eq!(
+            resolve_head("ref: refs/heads/packed\n", loose_ref, packed_refs),
+            Some("abc123".into())
+        );
+        assert_eq!(
+            resolve_head("ref: refs/heads/unborn\n", loose_ref, packed_refs),
+            None
+        );
+        assert_eq!(
+            resolve_head("789abc\n", loose_ref, packed_refs),
+            Some("789abc".into())
+        );
+    }
+
+    #[test]
+    fn round_trips_measurements() {
+        let measurement = measurement(Step::Parse, 1_701_388_800, 1500.0);
+        let json = JsonValue::from(&measurement);
+        assert_eq!(Measurement::try_from(&json), Ok(measurement));
+    }
+}
@@ -0,0 +1,169 @@
+/// Module that selects the input a solution binary runs on.
+///
+/// By default, a solution reads its puzzle input from `data/inputs/<day>.txt`, or from the first file of
+/// `data/inputs/<day>/` if there is none. This can be changed with:
+///  - `--input <path>` to read any file, or the standard input if the path is `-`.
+///  - `--example` to read `data/examples/<day>.txt`.
+///  - `--example-part <N>` to read `data/examples/<day>-<N>.txt`.
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
+
+use crate::template::{list_files, read_file, read_file_part, Day};
+
+const INPUT_FLAG: &str = "--input";
+const EXAMPLE_FLAG: &str = "--example";
+const EXAMPLE_PART_FLAG: &str = "--example-part";
+
+/// The input of a solution binary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, stored in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// A file given by path, `-` is the standard input.
+    File(String),
+    /// The example of the puzzle, stored in `data/examples`.
+    Example,
+    /// The example of a single part, stored in `data/examples`.
+    ExamplePart(u8),
+}
+
+impl InputSource {
+    /// Builds the input source from the values of the command-line flags, which are mutually exclusive.
+    pub fn new(
+        path: Option<String>,
+        example: bool,
+        example_part: Option<u8>,
+    ) -> Result<Self, String> {
+        match (path, example, example_part) {
+            (None, false, None) => Ok(InputSource::Puzzle),
+            (Some(path), false, None) => Ok(InputSource::File(path)),
+            (None, true, None) => Ok(InputSource::Example),
+            (None, false, Some(part)) => Ok(InputSource::ExamplePart(part)),
+            _ => Err(format!(
+                "{INPUT_FLAG}, {EXAMPLE_FLAG} and {EXAMPLE_PART_FLAG} cannot be used together."
+            )),
+        }
+    }
+
+    /// Reads the input source from the arguments of the solution binary, exits if they are invalid.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+
+    fn from_args(args: &[String]) -> Result<Self, String> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .map(|index| {
+                    args.get(index + 1)
+                        .ok_or(format!("Missing value for {flag}."))
+                })
+                .transpose()
+        };
+
+        let path = value(INPUT_FLAG)?.cloned();
+        let example = args.iter().any(|arg| arg == EXAMPLE_FLAG);
+        let example_part = value(EXAMPLE_PART_FLAG)?
+            .map(|part| {
+                part.parse::<u8>()
+                    .map_err(|_| format!("Expected {EXAMPLE_PART_FLAG} to be a part number."))
+            })
+            .transpose()?;
+
+        Self::new(path, example, example_part)
+    }
+
+    /// The command-line arguments to pass to a solution binary to select this input.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::File(path) => vec![INPUT_FLAG.into(), path.clone()],
+            InputSource::Example => vec![EXAMPLE_FLAG.into()],
+            InputSource::ExamplePart(part) => vec![EXAMPLE_PART_FLAG.into(), part.to_string()],
+        }
+    }
+
+    /// Whether this is the puzzle input, i.e. the one the stored and submitted answers are computed on.
+    pub fn is_puzzle(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// Reads the input of the given day.
+    #[must_use]
+    pub fn read(&self, day: Day) -> String {
+        match self {
+            InputSource::Puzzle => match list_files("inputs", day).first() {
+                Some(path) => fs::read_to_string(path).expect("could not open input file"),
+                None => read_file("inputs", day),
+            },
+            InputSource::File(path) if path == "-" => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read standard input");
+                input
+            }
+            InputSource::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            InputSource::Example => read_file("examples", day),
+            InputSource::ExamplePart(part) => read_file_part("examples", day, *part),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+
+    fn from_args(args: &[&str]) -> Result<InputSource, String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn reads_sources_from_args() {
+        assert_eq!(from_args(&["05"]), Ok(InputSource::Puzzle));
+        assert_eq!(
+            from_args(&["05", "--input", "-"]),
+            Ok(InputSource::File("-".into()))
+        );
+        assert_eq!(from_args(&["05", "--example"]), Ok(InputSource::Example));
+        assert_eq!(
+            from_args(&["05", "--time", "--example-part", "2"]),
+            Ok(InputSource::ExamplePart(2))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_args() {
+        assert_eq!(from_args(&["05", "--input"]).is_err(), true);
+        assert_eq!(from_args(&["05", "--example-part", "x"]).is_err(), true);
+        assert_eq!(
+            from_args(&["05", "--example", "--input", "in.txt"]).is_err(),
+            true
+        );
+    }
+
+    #[test]
+    fn round_trips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::File("in.txt".into()),
+            InputSource::Example,
+            InputSource::ExamplePart(1),
+        ] {
+            let mut args = vec!["05".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::from_args(&args), Ok(source));
+        }
+    }
+}
@@ -1,27 +1,73 @@
-use std::{env, fs};
+use std::{env, fs, io, path::PathBuf};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod export;
+pub mod history;
+pub mod input;
+pub mod protocol;
 pub mod runner;
 
 pub use day::*;
+pub use run_multi::parse_timeout;
+pub use solution::*;
+pub use year::*;
 
+mod baseline;
+mod benchmark_chart;
 mod day;
+mod downloads;
+mod examples;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
+mod solution;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the current year to a string, see [`data_dir`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returns an error if the file cannot be read.
+pub fn try_read_file(folder: &str, day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd.join(data_dir()).join(folder).join(format!("{day}.txt"));
+    fs::read_to_string(filepath)
+}
+
+/// Helper function that lists the files of a day: `<day>.txt` if it exists, then the `.txt` files of the `<day>`
+/// directory, sorted by name. E.g. `data/inputs/01.txt`, `data/inputs/01/alice.txt` and `data/inputs/01/bob.txt`.
+///
+/// The paths are relative to the working directory.
+#[must_use]
+pub fn list_files(folder: &str, day: Day) -> Vec<PathBuf> {
+    let folder = data_dir().join(folder);
+    let file = folder.join(format!("{day}.txt"));
+
+    let mut files: Vec<PathBuf> = fs::read_dir(folder.join(day.to_string()))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    files.sort();
+
+    if file.is_file() {
+        files.insert(0, file);
+    }
+    files
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -29,7 +75,7 @@ pub fn read_file(folder: &str, day: Day) -> String {
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir())
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
@@ -39,6 +85,14 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A parser can be provided with `parse = <function>`. In that case, the input is parsed once,
+/// the parsing is timed on its own and each part receives a reference to the parsed value.
+///
+/// ```ignore
+/// advent_of_code::solution!(2, parse = parse_input);
+/// advent_of_code::solution!(20, parse = parse_input, 1);
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +104,75 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The solution of the current day, to be run in-process.
+        // NOTE: only used when the solution is compiled in the library.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            day: DAY,
+            steps: &[$( $crate::template::protocol::Step::Part($part) ),*],
+            run: |input, is_timed, report| {
+                use $crate::template::runner::*;
+                $( report(solve_part($func, input, $part, is_timed)); )*
+            },
+        };
 
+        // NOTE: only used when the solution is compiled as a binary.
+        #[allow(dead_code)]
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+            $crate::template::Year::__select_bin(option_env!("CARGO_BIN_NAME"));
+            let input = $crate::template::input::InputSource::from_env().read(DAY);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        /// The solution of the current day, to be run in-process.
+        // NOTE: only used when the solution is compiled in the library.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            day: DAY,
+            steps: &[
+                $crate::template::protocol::Step::Parse,
+                $( $crate::template::protocol::Step::Part($part) ),*
+            ],
+            run: |input, is_timed, report| {
+                use $crate::template::runner::*;
+                let (parsed, parse_report) = solve_parse($parse, input, is_timed);
+                report(parse_report);
+                // NOTE: the parts cannot run without the parsed input.
+                let Some(parsed) = parsed else {
+                    return;
+                };
+                $( report(solve_part($func, &parsed, $part, is_timed)); )*
+            },
+        };
+
+        // NOTE: only used when the solution is compiled as a binary.
+        #[allow(dead_code)]
+        fn main() {
+            use $crate::template::runner::*;
+            $crate::template::Year::__select_bin(option_env!("CARGO_BIN_NAME"));
+            let input = $crate::template::input::InputSource::from_env().read(DAY);
+            let parsed = run_parse($parse, &input, DAY);
+            $( run_part($func, &parsed, DAY, $part); )*
+        }
+    };
 }
@@ -0,0 +1,323 @@
+/// Module that defines the machine-readable output of the solution binaries.
+///
+/// When a solution binary is called with `--json`, it prints one JSON object per line
+/// for each step it runs instead of the human readable output:
+///
+/// ```txt
+/// {"part":"parse","answer":null,"nanos":1500,"samples":1000,"status":"ok","stats":{...},"memory":null}
+/// {"part":1,"answer":"42","nanos":74130,"samples":100,"status":"ok","stats":{...},"memory":null}
+/// {"part":2,"answer":null,"nanos":310,"samples":1,"status":"unsolved","stats":{...},"memory":null}
+/// ```
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{alloc::AllocStats, stats::Summary};
+
+/// The command-line flag that enables the machine-readable output.
+pub const JSON_FLAG: &str = "--json";
+
+/// A step executed by a solution binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => f.write_str("Parse"),
+            Step::Part(part) => write!(f, "Part {part}"),
+        }
+    }
+}
+
+/// A step is serialized as `"parse"` or as the number of the part.
+impl From<Step> for JsonValue {
+    fn from(value: Step) -> Self {
+        match value {
+            Step::Parse => JsonValue::String("parse".into()),
+            Step::Part(part) => JsonValue::Number(part.into()),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for Step {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        match value {
+            JsonValue::String(s) if s == "parse" => Ok(Step::Parse),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            JsonValue::Number(n) if *n == 1.0 || *n == 2.0 => Ok(Step::Part(*n as u8)),
+            _ => Err("Expected step to be \"parse\", 1 or 2.".into()),
+        }
+    }
+}
+
+/// The outcome of a step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The step ran to completion and produced an answer (if any is expected).
+    Ok,
+    /// The solution returned `None`.
+    Unsolved,
+    /// The step ran out of time and was aborted, see [`run_multi`](crate::template::run_multi).
+    TimedOut,
+    /// The step panicked.
+    Panicked,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Unsolved => "unsolved",
+            Status::TimedOut => "timed_out",
+            Status::Panicked => "panicked",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ok" => Ok(Status::Ok),
+            "unsolved" => Ok(Status::Unsolved),
+            "timed_out" => Ok(Status::TimedOut),
+            "panicked" => Ok(Status::Panicked),
+            _ => Err(format!("unknown status `{s}`.")),
+        }
+    }
+}
+
+/// The result of a single step, as reported by a solution binary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub step: Step,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub summary: Summary,
+    /// Heap usage of the first run, only measured with the `alloc-stats` feature.
+    pub memory: Option<AllocStats>,
+    /// The location and message of the panic, if the step panicked.
+    pub panic: Option<String>,
+}
+
+impl Report {
+    /// The duration reported for this step, in nanoseconds.
+    pub fn nanos(&self) -> f64 {
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = self.summary.median.as_nanos() as f64;
+        nanos
+    }
+
+    /// The total number of samples taken for this step, including rejected outliers.
+    pub fn samples(&self) -> u128 {
+        self.summary.total_samples()
+    }
+
+    /// Serializes the report to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::from(self.step));
+        map.insert(
+            "answer".into(),
+            match &self.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), JsonValue::Number(self.nanos()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(self.samples() as f64));
+        map.insert(
+            "status".into(),
+            JsonValue::String(self.status.as_str().into()),
+        );
+        map.insert("stats".into(), JsonValue::from(&self.summary));
+        map.insert(
+            "memory".into(),
+            match &self.memory {
+                Some(memory) => JsonValue::from(memory),
+                None => JsonValue::Null,
+            },
+        );
+        if let Some(panic) = &self.panic {
+            map.insert("panic".into(), JsonValue::String(panic.clone()));
+        }
+
+        // NOTE: `stringify` never emits line breaks, strings are escaped.
+        JsonValue::Object(map)
+            .stringify()
+            .expect("report contains only finite numbers")
+    }
+
+    /// Parses a line printed by a solution binary.
+    ///
+    /// Returns [`None`] if the line is not a report, e.g. a debug print from a solution.
+    pub fn from_json_line(line: &str) -> Option<Result<Self, String>> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        let json = json.get::<HashMap<String, JsonValue>>()?;
+
+        if !json.contains_key("part") || !json.contains_key("status") {
+            return None;
+        }
+
+        Some(Self::try_from(json))
+    }
+}
+
+impl TryFrom<&HashMap<String, JsonValue>> for Report {
+    type Error = String;
+
+    fn try_from(json: &HashMap<String, JsonValue>) -> Result<Self, Self::Error> {
+        let step = json
+            .get("part")
+            .and_then(|v| Step::try_from(v).ok())
+            .ok_or("Expected report.part to be \"parse\", 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?
+            .cloned();
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let summary = json
+            .get("stats")
+            .ok_or("Expected report.stats to be present.")
+            .map(Summary::try_from)??;
+
+        let memory = json
+            .get("memory")
+            .filter(|v| !v.is_null())
+            .map(AllocStats::try_from)
+            .transpose()?;
+
+        let panic = json
+            .get("panic")
+            .filter(|v| !v.is_null())
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected report.panic to be a string.")
+            })
+            .transpose()?
+            .cloned();
+
+        Ok(Report {
+            step,
+            answer,
+            status,
+            summary,
+            memory,
+            panic,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Report, Status, Step};
+    use crate::template::{alloc::AllocStats, stats::Summary};
+
+    fn report(step: Step, answer: Option<&str>) -> Report {
+        Report {
+            step,
+            answer: answer.map(Into::into),
+            status: if answer.is_some() || step == Step::Parse {
+                Status::Ok
+            } else {
+                Status::Unsolved
+            },
+            summary: Summary::single(Duration::from_micros(74)),
+            memory: None,
+            panic: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_reports() {
+        for report in [
+            report(Step::Parse, None),
+            report(Step::Part(1), Some("42")),
+            report(Step::Part(2), None),
+        ] {
+            let line = report.to_json_line();
+            assert_eq!(line.contains('\n'), false);
+            assert_eq!(Report::from_json_line(&line).unwrap().unwrap(), report);
+        }
+    }
+
+    #[test]
+    fn round_trips_memory() {
+        let report = Report {
+            memory: Some(AllocStats {
+                peak_bytes: 4096,
+                allocations: 12,
+            }),
+            ..report(Step::Part(1), Some("42"))
+        };
+        let line = report.to_json_line();
+        assert_eq!(Report::from_json_line(&line).unwrap().unwrap(), report);
+    }
+
+    #[test]
+    fn round_trips_panics() {
+        let report = Report {
+            status: Status::Panicked,
+            panic: Some("src/bin/01.rs:12:5: attempt to subtract with overflow".into()),
+            ..report(Step::Part(1), None)
+        };
+        let line = report.to_json_line();
+        assert_eq!(Report::from_json_line(&line).unwrap().unwrap(), report);
+    }
+
+    #[test]
+    fn handles_patterns_in_answers() {
+        let report = report(Step::Part(1), Some("Part 2: (2s @ 5 samples)\n\"}{"));
+        let line = report.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        let parsed = Report::from_json_line(&line).unwrap().unwrap();
+        assert_eq!(parsed.answer, report.answer);
+        assert_eq!(parsed.nanos(), 74_000_f64);
+        assert_eq!(parsed.samples(), 1);
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        assert_eq!(Report::from_json_line("Part 1: 42 (1ms)"), None);
+        assert_eq!(Report::from_json_line(""), None);
+        assert_eq!(Report::from_json_line("[1, 2, 3]"), None);
+        assert_eq!(Report::from_json_line(r#"{ "foo": 1 }"#), None);
+    }
+
+    #[test]
+    fn errors_for_malformed_reports() {
+        let line = r#"{ "part": 3, "answer": null, "status": "ok", "stats": null }"#;
+        assert!(Report::from_json_line(line).unwrap().is_err());
+    }
+
+    #[test]
+    fn rejects_fractional_and_out_of_range_parts() {
+        assert_eq!(Step::try_from(&JsonValue::Number(2.0)), Ok(Step::Part(2)));
+        for part in [1.5, 257.0, -255.0, 0.0] {
+            assert!(Step::try_from(&JsonValue::Number(part)).is_err());
+        }
+    }
+}
@@ -1,9 +1,13 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, str::FromStr};
 
-use super::timings::Timings;
-use super::Day;
+use super::alloc::format_bytes;
+use super::answers::Check;
+use super::protocol::{Status, Step};
+use super::timings::{StepTiming, Timing, Timings};
+use super::{get_path_for_bin, read_readme, readme_path, Day};
+use crate::solutions;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -20,17 +24,13 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(super) pos_start: usize,
+    pub(super) pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates the block of the readme enclosed by two markers, markers included.
+pub(super) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,78 +51,283 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// A column of the benchmark table, besides the day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    /// The peak heap usage of each part, only measured with the `alloc-stats` feature.
+    Memory,
+    /// The number of samples each part was benched with.
+    Samples,
+    /// Whether the answer of each part matches the accepted one.
+    Check,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "memory" => Ok(Column::Memory),
+            "samples" => Ok(Column::Samples),
+            "check" => Ok(Column::Check),
+            _ => Err(format!(
+                "unknown benchmark column `{s}`, expected one of parse, part_1, part_2, memory, samples or check."
+            )),
+        }
+    }
+}
+
+impl Column {
+    fn header(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Memory => "Memory",
+            Column::Samples => "Samples",
+            Column::Check => "Check",
+        }
+    }
+
+    fn cell(self, timing: &Timing) -> String {
+        let parts = |cell: fn(&StepTiming) -> Option<String>| {
+            [&timing.part_1, &timing.part_2]
+                .map(|step| step.as_ref().and_then(cell).unwrap_or_else(|| "-".into()))
+                .join(" / ")
+        };
+
+        let step = |step: Step| step_cell(timing.day, step, timing.step(step), timing.status(step));
+
+        match self {
+            Column::Parse => step(Step::Parse),
+            Column::Part1 => step(Step::Part(1)),
+            Column::Part2 => step(Step::Part(2)),
+            Column::Memory => parts(|step| step.memory.map(|m| format_bytes(m.peak_bytes))),
+            Column::Samples => parts(|step| (step.samples > 0).then(|| step.samples.to_string())),
+            Column::Check => parts(|step| step.check.as_ref().map(check_symbol)),
+        }
+    }
+}
+
+/// The order of the rows of the benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Day,
+    /// The slowest days first.
+    Cost,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(Sort::Day),
+            "cost" => Ok(Sort::Cost),
+            _ => Err(format!(
+                "unknown benchmark sort `{s}`, expected `day` or `cost`."
+            )),
+        }
+    }
+}
+
+/// The layout of the benchmark table.
+///
+/// Configured with the `AOC_BENCHMARK_COLUMNS` (comma-separated) and `AOC_BENCHMARK_SORT` environment variables,
+/// see `.cargo/config.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Parse, Column::Part1, Column::Part2],
+            sort: Sort::Day,
+        }
+    }
+}
+
+impl TableOptions {
+    pub fn from_env() -> Result<Self, Error> {
+        Self::new(
+            env::var("AOC_BENCHMARK_COLUMNS").ok().as_deref(),
+            env::var("AOC_BENCHMARK_SORT").ok().as_deref(),
+        )
+        .map_err(Error::Parser)
+    }
+
+    fn new(columns: Option<&str>, sort: Option<&str>) -> Result<Self, String> {
+        let default = Self::default();
+
+        let columns = match columns.filter(|c| !c.trim().is_empty()) {
+            Some(columns) => columns
+                .split(',')
+                .map(Column::from_str)
+                .collect::<Result<_, _>>()?,
+            None => default.columns,
+        };
+
+        let sort = match sort.filter(|s| !s.trim().is_empty()) {
+            Some(sort) => sort.parse()?,
+            None => default.sort,
+        };
+
+        Ok(Self { columns, sort })
+    }
+}
+
+/// NOTE: the parts the solution doesn't declare, e.g. the second part of `solution!(12, 1)`, never report a status.
+fn step_cell(day: Day, step: Step, timing: Option<&StepTiming>, status: Option<Status>) -> String {
+    let is_declared = step == Step::Parse
+        || solutions::get(day).is_none_or(|solution| solution.steps.contains(&step));
+    let status = status.or((!is_declared).then_some(Status::Unsolved));
+
+    match (timing, status) {
+        (Some(step), _) => format!("`{:.1?}`", step.duration()),
+        (None, Some(Status::Unsolved)) => "_not implemented_".into(),
+        (None, Some(Status::TimedOut)) => "_timed out_".into(),
+        (None, Some(Status::Panicked)) => "_panicked_".into(),
+        (None, _) => "`-`".into(),
+    }
+}
+
+/// NOTE: the expected answer is left out on purpose, answers are not meant to be published.
+fn check_symbol(check: &Check) -> String {
+    match check {
+        Check::Match => "✔",
+        Check::Mismatch { .. } => "✘",
+        Check::Unknown => "?",
+    }
+    .into()
+}
+
+/// Builds the benchmark table, under a `{prefix} Benchmarks` heading.
+pub(super) fn construct_table(
+    prefix: &str,
+    mut timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!(
+            "| Day |{}",
+            options
+                .columns
+                .iter()
+                .map(|column| format!(" {} |", column.header()))
+                .collect::<String>()
+        ),
+        format!("| :---: |{}", " :---: |".repeat(options.columns.len())),
     ];
 
+    match options.sort {
+        Sort::Day => timings.data.sort_by_key(|timing| timing.day),
+        Sort::Cost => timings
+            .data
+            .sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos)),
+    }
+
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            options
+                .columns
+                .iter()
+                .map(|column| format!(" {} |", column.cell(&timing)))
+                .collect::<String>()
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, options);
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &format!("{MARKER}\n{table}\n{MARKER}"),
+    );
     Ok(())
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let options = TableOptions::from_env()?;
+    let mut readme = read_readme(MARKER)?;
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    update_content(&mut readme, timings, total_millis, &options)?;
+    fs::write(readme_path(), &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::time::Duration;
+
+    use super::{update_content, Column, Sort, TableOptions, MARKER};
+    use crate::{
+        day,
+        template::{
+            alloc::AllocStats,
+            answers::Check,
+            protocol::Status,
+            timings::{StepTiming, Timing, Timings},
+        },
+    };
+
+    fn step(millis: u64) -> Option<StepTiming> {
+        Some(StepTiming::from_duration(Duration::from_millis(millis)))
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: step(10),
+                    part_2: step(20),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: step(5),
+                    part_1: step(30),
+                    part_2: step(40),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: step(40),
+                    part_2: step(50),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
@@ -130,28 +335,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,18 +364,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +384,96 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn marks_undeclared_parts_as_not_implemented() {
+        // NOTE: day 12 is declared with `solution!(12, 1)`.
+        let timings = Timings {
+            data: vec![Timing {
+                part_1: step(10),
+                part_1_status: Some(Status::Ok),
+                total_nanos: 1e+7,
+                ..Timing::new(day!(12))
+            }],
+            history: vec![],
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 10.0, &TableOptions::default()).unwrap();
+        assert_eq!(
+            s.contains("| [Day 12](./src/bin/12.rs) | `-` | `10.0ms` | _not implemented_ |"),
+            true
+        );
+    }
+
+    #[test]
+    fn reads_table_options() {
+        assert_eq!(TableOptions::new(None, None), Ok(TableOptions::default()));
+        assert_eq!(
+            TableOptions::new(Some("part_1, part_2,memory,samples,check"), Some("cost")),
+            Ok(TableOptions {
+                columns: vec![
+                    Column::Part1,
+                    Column::Part2,
+                    Column::Memory,
+                    Column::Samples,
+                    Column::Check
+                ],
+                sort: Sort::Cost,
+            })
+        );
+        assert_eq!(TableOptions::new(Some("part_3"), None).is_err(), true);
+        assert_eq!(TableOptions::new(None, Some("name")).is_err(), true);
+    }
+
+    #[test]
+    fn formats_configured_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2 = None;
+        timings.data[0].part_2_status = Some(Status::Unsolved);
+        timings.data[1].part_1 = Some(StepTiming {
+            samples: 100,
+            memory: Some(AllocStats {
+                peak_bytes: 2048,
+                allocations: 3,
+            }),
+            check: Some(Check::Match),
+            ..step(30).unwrap()
+        });
+        timings.data[1].part_2 = Some(StepTiming {
+            check: Some(Check::Mismatch {
+                expected: "42".into(),
+            }),
+            ..step(40).unwrap()
+        });
+
+        let options = TableOptions {
+            columns: vec![
+                Column::Part1,
+                Column::Part2,
+                Column::Memory,
+                Column::Samples,
+                Column::Check,
+            ],
+            sort: Sort::Cost,
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Memory | Samples | Check |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` | - / - | 1 / 1 | - / - |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | 2.0 KiB / - | 100 / 1 | ✔ / ✘ |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | _not implemented_ | - / - | 1 / - | - / - |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
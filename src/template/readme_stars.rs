@@ -0,0 +1,202 @@
+/// Module that updates the readme with the stars earned on each day.
+/// The table has the same layout as the one of `aoc-readme-stars`, but the stars are computed locally
+/// from the accepted answers stored in `data/answers`.
+/// The stars already shown in the readme are kept, the answers of the days solved before the store existed
+/// are unknown.
+use std::fs;
+
+use super::answers::{get_answers_dir, read_answer};
+use super::readme_benchmarks::{locate_table, Error};
+use super::{all_days, read_readme, readme_path, Day, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// The stars earned on a day, one per part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub parts: [bool; 2],
+}
+
+/// Collects the stars of every day, up to the last day with a star.
+pub fn collect_stars(has_answer: impl Fn(Day, u8) -> bool) -> Vec<DayStars> {
+    let mut stars: Vec<DayStars> = all_days()
+        .map(|day| DayStars {
+            day,
+            parts: [has_answer(day, 1), has_answer(day, 2)],
+        })
+        .collect();
+
+    // NOTE: the second star of the last day has no answer, it is given once all the other stars are earned.
+    let earned = stars.iter().flat_map(|s| s.parts).filter(|x| *x).count();
+    if let Some(last) = stars.last_mut() {
+        if earned == 2 * 25 - 1 && last.parts[0] {
+            last.parts[1] = true;
+        }
+    }
+
+    let len = stars
+        .iter()
+        .rposition(|s| s.parts.contains(&true))
+        .map_or(0, |i| i + 1);
+    stars.truncate(len);
+    stars
+}
+
+/// Reads the stars shown in the table of a readme.
+fn parse_stars(readme: &str) -> Vec<DayStars> {
+    let Ok(positions) = locate_table(readme, MARKER) else {
+        return vec![];
+    };
+
+    readme[positions.pos_start..positions.pos_end]
+        .lines()
+        .filter_map(|line| {
+            // e.g. `| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |`
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+            let ["", day, part_1, part_2, ""] = cells[..] else {
+                return None;
+            };
+            let (day, _) = day.strip_prefix("[Day ")?.split_once(']')?;
+            let day = Day::new(day.parse().ok()?)?;
+            Some(DayStars {
+                day,
+                parts: [part_1 == "⭐", part_2 == "⭐"],
+            })
+        })
+        .collect()
+}
+
+fn construct_table(prefix: &str, year: u16, stars: &[DayStars]) -> String {
+    let header = format!("{prefix} {year} Results");
+    let star = |earned: bool| if earned { "⭐" } else { " " };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for DayStars { day, parts } in stars {
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} |",
+            day.into_inner(),
+            day.into_inner(),
+            star(parts[0]),
+            star(parts[1])
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, stars: &[DayStars]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, stars);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update() -> Result<(), Error> {
+    let year = Year::current().ok_or_else(|| Error::Parser("AOC_YEAR is not set.".into()))?;
+    let mut readme = read_readme(MARKER)?;
+
+    // NOTE: an empty store would otherwise wipe the stars shown in the readme.
+    if collect_stars(|day, part| read_answer(day, part).is_some()).is_empty() {
+        return Err(Error::Parser(format!(
+            "no answers are stored in \"{}\", the table is left as is.",
+            get_answers_dir().display()
+        )));
+    }
+
+    let shown = parse_stars(&readme);
+    let is_shown = |day: Day, part: u8| {
+        shown
+            .iter()
+            .any(|stars| stars.day == day && stars.parts[usize::from(part - 1)])
+    };
+    let stars = collect_stars(|day, part| read_answer(day, part).is_some() || is_shown(day, part));
+    update_content(&mut readme, year.into_inner(), &stars)?;
+    fs::write(readme_path(), &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect_stars, parse_stars, update_content, DayStars, MARKER};
+    use crate::day;
+
+    #[test]
+    fn collects_stars_up_to_the_last_day() {
+        let stars =
+            collect_stars(|day, part| day.into_inner() <= 3 && (part == 1 || day != day!(2)));
+        assert_eq!(
+            stars,
+            [
+                DayStars {
+                    day: day!(1),
+                    parts: [true, true]
+                },
+                DayStars {
+                    day: day!(2),
+                    parts: [true, false]
+                },
+                DayStars {
+                    day: day!(3),
+                    parts: [true, true]
+                },
+            ]
+        );
+        assert_eq!(collect_stars(|_, _| false), []);
+    }
+
+    #[test]
+    fn gives_the_last_star_with_all_the_others() {
+        let stars = collect_stars(|day, part| part == 1 || day != day!(25));
+        assert_eq!(stars.last().unwrap().parts, [true, true]);
+
+        let stars = collect_stars(|day, part| part == 1 || day.into_inner() < 24);
+        assert_eq!(stars.last().unwrap().parts, [true, false]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, 2023, &[]).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\n| old |\n{}\nbaz", MARKER, MARKER);
+        let stars = collect_stars(|day, part| day == day!(1) || (day == day!(2) && part == 1));
+        update_content(&mut s, 2023, &stars).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn parses_shown_stars() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let stars = collect_stars(|day, part| day == day!(1) || (day == day!(12) && part == 1));
+        update_content(&mut s, 2023, &stars).unwrap();
+
+        assert_eq!(parse_stars(&s), stars);
+        assert_eq!(parse_stars("# readme"), []);
+    }
+}
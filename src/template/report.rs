@@ -0,0 +1,298 @@
+/// Module that writes a static HTML report of every day, to be shared instead of the readme.
+///
+/// The report is a single file with inline styles, built from the solutions registry, the stored timings,
+/// the accepted answers in `data/answers` and the puzzle descriptions in `data/puzzles`.
+use std::{fmt::Write, fs, io, time::Duration};
+
+use super::answers::{read_answer, Check};
+use super::downloads::get_puzzle_path;
+use super::protocol::{Status, Step};
+use super::timings::{StepTiming, Timing, Timings};
+use super::{all_days, get_path_for_bin, Day};
+use crate::solutions;
+
+pub static DEFAULT_REPORT_PATH: &str = "./report.html";
+
+static STYLE: &str = "
+body { font-family: sans-serif; margin: 2rem auto; max-width: 72rem; color: #222; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border-bottom: 1px solid #ddd; padding: 0.4rem 0.6rem; text-align: left; vertical-align: top; }
+th { background: #f4f4f4; }
+code, pre { font-size: 0.9rem; margin: 0; }
+.time { color: #666; font-size: 0.85rem; }
+.match { color: #2a7d2a; }
+.mismatch, .error { color: #b22222; }
+.unknown, .missing { color: #999; }
+";
+
+/// Everything the report shows about a day.
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub day: Day,
+    /// The title of the puzzle, if its description has been downloaded.
+    pub title: Option<String>,
+    /// The steps of the solution, if the day has been scaffolded.
+    pub steps: Option<&'static [Step]>,
+    pub timing: Option<Timing>,
+    /// The accepted answer of each part.
+    pub answers: [Option<String>; 2],
+}
+
+impl DayReport {
+    fn is_implemented(&self, part: u8) -> bool {
+        let status = match part {
+            1 => self.timing.as_ref().and_then(|t| t.part_1_status),
+            _ => self.timing.as_ref().and_then(|t| t.part_2_status),
+        };
+        let in_steps = self
+            .steps
+            .is_none_or(|steps| steps.contains(&Step::Part(part)));
+        in_steps && status != Some(Status::Unsolved)
+    }
+}
+
+/// Collects the days that have been scaffolded or timed.
+pub fn collect(
+    timings: &Timings,
+    read_title: impl Fn(Day) -> Option<String>,
+    read_answer: impl Fn(Day, u8) -> Option<String>,
+) -> Vec<DayReport> {
+    all_days()
+        .filter_map(|day| {
+            let steps = solutions::get(day).map(|solution| solution.steps);
+            let timing = timings.data.iter().find(|t| t.day == day).cloned();
+
+            (steps.is_some() || timing.is_some()).then(|| DayReport {
+                day,
+                title: read_title(day),
+                steps,
+                timing,
+                answers: [read_answer(day, 1), read_answer(day, 2)],
+            })
+        })
+        .collect()
+}
+
+/// Reads the title of a puzzle from the first line of its description, e.g. `## --- Day 1: Trebuchet?! ---`.
+pub fn parse_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let line = line.trim_start_matches('#').trim();
+        let line = line.strip_prefix("---")?.strip_suffix("---")?.trim();
+        let (_, title) = line.strip_prefix("Day ")?.split_once(':')?;
+        Some(title.trim().to_owned())
+    })
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_answer(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer.contains('\n') => format!("<pre>{}</pre>", escape(answer)),
+        Some(answer) => format!("<code>{}</code>", escape(answer)),
+        None => r#"<span class="missing">-</span>"#.into(),
+    }
+}
+
+fn format_step(step: Option<&StepTiming>, status: Option<Status>) -> String {
+    let Some(step) = step else {
+        return match status {
+            Some(Status::TimedOut) => r#"<span class="error">timed out</span>"#.into(),
+            Some(Status::Panicked) => r#"<span class="error">panicked</span>"#.into(),
+            _ => String::new(),
+        };
+    };
+
+    let check = match &step.check {
+        Some(Check::Match) => {
+            r#" <span class="match" title="matches the accepted answer">✔</span>"#
+        }
+        Some(Check::Mismatch { .. }) => {
+            r#" <span class="mismatch" title="differs from the accepted answer">✘</span>"#
+        }
+        Some(Check::Unknown) => r#" <span class="unknown" title="no accepted answer">?</span>"#,
+        None => "",
+    };
+
+    let samples = match step.samples {
+        0 => String::new(),
+        samples => format!(r#" title="median of {samples} samples""#),
+    };
+
+    format!(
+        r#"{check}<br><span class="time"{samples}>{:.1?}</span>"#,
+        step.duration()
+    )
+}
+
+fn format_part(report: &DayReport, part: u8) -> String {
+    if !report.is_implemented(part) {
+        return r#"<span class="missing">not implemented</span>"#.into();
+    }
+
+    let (step, status) = match (&report.timing, part) {
+        (Some(t), 1) => (t.part_1.as_ref(), t.part_1_status),
+        (Some(t), _) => (t.part_2.as_ref(), t.part_2_status),
+        (None, _) => (None, None),
+    };
+
+    format_answer(report.answers[usize::from(part - 1)].as_deref()) + &format_step(step, status)
+}
+
+/// Renders the report of the days.
+pub fn render(year: Option<u16>, days: &[DayReport]) -> String {
+    let title = match year {
+        Some(year) => format!("Advent of Code {year}"),
+        None => "Advent of Code".into(),
+    };
+
+    let stars = days.iter().flat_map(|d| &d.answers).flatten().count();
+    let total_millis = days
+        .iter()
+        .filter_map(|d| d.timing.as_ref())
+        .map(|t| t.total_nanos)
+        .sum::<f64>()
+        / 1_000_000_f64;
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, r#"<html lang="en">"#);
+    let _ = writeln!(
+        html,
+        r#"<head><meta charset="utf-8"><title>{title}</title><style>{STYLE}</style></head>"#
+    );
+    let _ = writeln!(html, "<body>");
+    let _ = writeln!(html, "<h1>{title}</h1>");
+    let _ = writeln!(
+        html,
+        "<p>{stars} ⭐ · {} days · total {total_millis:.2}ms</p>",
+        days.len()
+    );
+    let _ = writeln!(html, "<table>");
+    let _ = writeln!(
+        html,
+        "<tr><th>Day</th><th>Puzzle</th><th>Part 1</th><th>Part 2</th><th>Parse</th><th>Total</th></tr>"
+    );
+
+    for report in days {
+        let link = match year {
+            Some(year) => format!(
+                r#"<a href="https://adventofcode.com/{year}/day/{}">"#,
+                report.day.into_inner()
+            ),
+            None => "<a>".into(),
+        };
+        let title = report.title.as_deref().map_or("puzzle".into(), escape);
+        let parse = report
+            .timing
+            .as_ref()
+            .and_then(|t| t.parse.as_ref())
+            .map(|step| format!(r#"<span class="time">{:.1?}</span>"#, step.duration()))
+            .unwrap_or_default();
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let total = report
+            .timing
+            .as_ref()
+            .map(|t| {
+                let total = Duration::from_nanos(t.total_nanos as u64);
+                format!(r#"<span class="time">{total:.1?}</span>"#)
+            })
+            .unwrap_or_default();
+
+        let _ = writeln!(
+            html,
+            r#"<tr><td><a href="{}">Day {}</a></td><td>{link}{title}</a></td><td>{}</td><td>{}</td><td>{parse}</td><td>{total}</td></tr>"#,
+            get_path_for_bin(report.day),
+            report.day.into_inner(),
+            format_part(report, 1),
+            format_part(report, 2),
+        );
+    }
+
+    let _ = writeln!(html, "</table>");
+    let _ = writeln!(html, "</body>");
+    let _ = writeln!(html, "</html>");
+    html
+}
+
+/// Writes the report of every day to the given path.
+pub fn write(path: &str, year: Option<u16>, timings: &Timings) -> Result<(), io::Error> {
+    let read_title = |day: Day| {
+        fs::read_to_string(get_puzzle_path(day))
+            .ok()
+            .and_then(|markdown| parse_title(&markdown))
+    };
+    let days = collect(timings, read_title, read_answer);
+    fs::write(path, render(year, &days))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{collect, parse_title, render};
+    use crate::{
+        day,
+        template::{
+            answers::Check,
+            protocol::Status,
+            timings::{StepTiming, Timing, Timings},
+        },
+    };
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("## --- Day 1: Trebuchet?! ---\n\nSomething is wrong."),
+            Some("Trebuchet?!".into())
+        );
+        assert_eq!(
+            parse_title("\n## --- Day 12: Hot Springs ---"),
+            Some("Hot Springs".into())
+        );
+        assert_eq!(parse_title("# Day 1"), None);
+    }
+
+    #[test]
+    fn renders_days() {
+        let timings = Timings {
+            data: vec![Timing {
+                part_1: Some(StepTiming {
+                    check: Some(Check::Match),
+                    ..StepTiming::from_duration(Duration::from_micros(4700))
+                }),
+                part_1_status: Some(Status::Ok),
+                part_2_status: Some(Status::Unsolved),
+                total_nanos: 4_700_000.0,
+                ..Timing::new(day!(12))
+            }],
+            history: vec![],
+        };
+
+        let days = collect(
+            &timings,
+            |day| (day == day!(12)).then(|| "Hot <Springs>".into()),
+            |day, part| (day == day!(12) && part == 1).then(|| "7195".into()),
+        );
+        let day_12 = days.iter().find(|d| d.day == day!(12)).unwrap();
+        assert_eq!(day_12.answers, [Some("7195".into()), None]);
+
+        let html = render(Some(2023), &days);
+        assert_eq!(html.starts_with("<!DOCTYPE html>"), true);
+        assert_eq!(html.contains("Hot &lt;Springs&gt;"), true);
+        assert_eq!(
+            html.contains(r#"<a href="./src/bin/12.rs">Day 12</a>"#),
+            true
+        );
+        assert_eq!(html.contains("<code>7195</code>"), true);
+        assert_eq!(html.contains("✔"), true);
+        assert_eq!(html.contains("4.7ms"), true);
+        assert_eq!(html.contains("not implemented"), true);
+    }
+}
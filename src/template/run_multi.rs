@@ -1,40 +1,124 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::solutions;
+use crate::template::input::InputSource;
+use crate::template::protocol::{Report, Status, Step};
+use crate::template::runner::{check_report, format_report};
+use crate::template::stats::Summary;
+use crate::template::{data_dir, list_files, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{StepTiming, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Options of [`run_multi`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Builds the solution binaries in release mode. Only used with `is_isolated`.
+    pub is_release: bool,
+    /// Benches every part of the solutions.
+    pub is_timed: bool,
+    /// Runs each solution in its own binary instead of in-process.
+    pub is_isolated: bool,
+    /// Aborts a solution when one of its steps runs longer than this, benchmark included.
+    /// Timed runs with a timeout are isolated, see [`RunOptions::is_isolated`].
+    pub timeout: Option<Duration>,
+    /// The number of days to run at once.
+    /// Defaults to a single day. Days run in-process one at a time with the `alloc-stats` feature,
+    /// as the allocation counters are shared by every thread.
+    pub jobs: Option<usize>,
+}
+
+impl RunOptions {
+    /// Whether the solutions run in their own binaries.
+    ///
+    /// A step that times out in-process is abandoned on its thread, which keeps running until the end of the run.
+    /// It would skew the timings of the next days, so timed runs with a timeout are always isolated.
+    fn is_isolated(&self) -> bool {
+        self.is_isolated || (self.is_timed && self.timeout.is_some())
+    }
+
+    fn jobs(&self) -> usize {
+        if cfg!(feature = "alloc-stats") && !self.is_isolated() {
+            return 1;
+        }
+        self.jobs.unwrap_or(1)
+    }
+}
+
+/// Parses a timeout such as `500ms`, `10s` or `2m`. A plain number is a number of seconds.
+pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(digits);
+
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid timeout `{s}`, expected e.g. `10s`."))?;
+
+    match unit {
+        "ms" => Ok(Duration::from_millis(value)),
+        "" | "s" => Ok(Duration::from_secs(value)),
+        "m" => Ok(Duration::from_secs(value * 60)),
+        _ => Err(format!("unknown unit `{unit}`, expected `ms`, `s` or `m`.")),
+    }
+}
+
+/// Outcome of [`run_multi`].
+#[derive(Debug, Default)]
+pub struct MultiRun {
+    /// The timings of the days that ran, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// The parts whose answer differs from the stored one.
+    pub mismatches: Vec<(Day, u8)>,
+}
+
+pub fn run_multi(days_to_run: HashSet<Day>, options: RunOptions) -> MultiRun {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut mismatches: Vec<(Day, u8)> = vec![];
 
-    let mut need_space = false;
     // NOTE: we didn't want duplicate day value, but we want days to be sorted.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    run_days(&days, options, |day, reports| {
+        if !reports.is_empty() {
+            timings.push(timing_from_reports(&reports, day));
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        for report in &reports {
+            if let (Step::Part(part), Some(check)) = (report.step, check_report(report, day)) {
+                if check.is_mismatch() {
+                    mismatches.push((day, part));
+                }
             }
-        });
+        }
+    });
+
+    if !mismatches.is_empty() {
+        let parts = mismatches
+            .iter()
+            .map(|(day, part)| format!("day {day} part {part}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("\n{ANSI_BOLD}Mismatching answers:{ANSI_RESET} {parts}");
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = if options.is_timed {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -42,9 +126,402 @@ pub fn run_multi(days_to_run: HashSet<Day>, is_release: bool, is_timed: bool) ->
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        mismatches,
+    }
+}
+
+/// Runs the days and passes their reports to `on_day`, in day order.
+///
+/// With more than one job, the days run on as many threads and the output of each day is buffered
+/// until the days before it have been printed.
+fn run_days(days: &[Day], options: RunOptions, mut on_day: impl FnMut(Day, Vec<Report>)) {
+    let jobs = options.jobs().clamp(1, days.len().max(1));
+
+    if jobs == 1 {
+        for (index, &day) in days.iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            on_day(day, run_day(day, options, &mut Output::Print));
+        }
+        return;
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let next_index = &next_index;
+
+            scope.spawn(move || {
+                while let Some(&day) = days.get(next_index.fetch_add(1, Ordering::Relaxed)) {
+                    let mut output = Output::Buffer(vec![]);
+                    let reports = run_day(day, options, &mut output);
+                    if sender.send((day, reports, output)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        // the days that completed before the ones preceding them.
+        let mut pending = BTreeMap::new();
+        let mut remaining = days.iter().enumerate();
+        let mut next = remaining.next();
+
+        for (day, reports, output) in receiver {
+            pending.insert(day, (reports, output));
+
+            while let Some((index, day)) = next {
+                let Some((reports, output)) = pending.remove(day) else {
+                    break;
+                };
+                if index > 0 {
+                    println!();
+                }
+                output.flush();
+                on_day(*day, reports);
+                next = remaining.next();
+            }
+        }
+    });
+}
+
+/// Runs the solution of a day and returns its reports.
+///
+/// When the day has several inputs, the solution runs on each of them and the spread of the timings is printed.
+/// Only the reports of the first input are returned: its answers are the ones checked and its timings the ones stored.
+fn run_day(day: Day, options: RunOptions, output: &mut Output) -> Vec<Report> {
+    output.println(format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+    output.println("------".into());
+
+    let inputs = list_files("inputs", day);
+
+    let reports = if inputs.len() <= 1 {
+        run_input(
+            day,
+            inputs.first().map(PathBuf::as_path),
+            true,
+            options,
+            output,
+        )
+    } else {
+        let mut runs: Vec<(String, Vec<Report>)> = Vec::with_capacity(inputs.len());
+
+        for (index, path) in inputs.iter().enumerate() {
+            let name = get_input_name(path);
+            if index > 0 {
+                output.println(String::new());
+            }
+            output.println(format!("{ANSI_ITALIC}{name}{ANSI_RESET}"));
+            let reports = run_input(day, Some(path), index == 0, options, output);
+            runs.push((name, reports));
+        }
+
+        if options.is_timed {
+            output.println(String::new());
+            format_spread(&runs)
+                .into_iter()
+                .for_each(|line| output.println(line));
+        }
+
+        runs.swap_remove(0).1
+    };
+
+    if reports.is_empty() {
+        output.println("Not solved.".into());
+    }
+
+    reports
+}
+
+/// Runs the solution of a day on one of its inputs.
+/// The answers are only checked for the first input of the day, the stored ones are computed on it.
+fn run_input(
+    day: Day,
+    input: Option<&Path>,
+    is_checked: bool,
+    options: RunOptions,
+    output: &mut Output,
+) -> Vec<Report> {
+    if options.is_isolated() {
+        let source = match input {
+            Some(path) if !is_checked => InputSource::File(path.to_string_lossy().into()),
+            _ => InputSource::Puzzle,
+        };
+        child_commands::run_solution(
+            day,
+            &source,
+            is_checked,
+            options.is_timed,
+            options.is_release,
+            options.timeout,
+            output,
+        )
+        .unwrap()
+    } else {
+        run_in_process(
+            day,
+            input,
+            is_checked,
+            options.is_timed,
+            options.timeout,
+            output,
+        )
+    }
+}
+
+/// The name of an input relative to `data/inputs`, e.g. `01.txt` or `01/alice.txt`.
+fn get_input_name(path: &Path) -> String {
+    let name = path.strip_prefix(data_dir().join("inputs")).unwrap_or(path);
+    name.to_string_lossy().replace('\\', "/")
+}
+
+/// Formats the fastest and slowest time of each step over the inputs of a day.
+/// The steps that did not complete on at least two inputs are left out.
+fn format_spread(runs: &[(String, Vec<Report>)]) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}Spread over {} inputs:{ANSI_RESET}",
+        runs.len()
+    )];
+
+    let mut steps: Vec<Step> = runs
+        .iter()
+        .flat_map(|(_, reports)| reports.iter().map(|report| report.step))
+        .collect();
+    steps.sort();
+    steps.dedup();
+
+    for step in steps {
+        let times: Vec<(&str, &Report)> = runs
+            .iter()
+            .filter_map(|(name, reports)| {
+                let report = reports
+                    .iter()
+                    .find(|r| r.step == step && r.status == Status::Ok)?;
+                Some((name.as_str(), report))
+            })
+            .collect();
+
+        // a spread needs at least two timings.
+        if times.len() < 2 {
+            continue;
+        }
+
+        let fastest = times
+            .iter()
+            .min_by(|a, b| a.1.nanos().total_cmp(&b.1.nanos()));
+        let slowest = times
+            .iter()
+            .max_by(|a, b| a.1.nanos().total_cmp(&b.1.nanos()));
+
+        let (Some((fastest_name, fastest)), Some((slowest_name, slowest))) = (fastest, slowest)
+        else {
+            continue;
+        };
+
+        lines.push(format!(
+            "{step}: {:.1?} ({fastest_name}) to {:.1?} ({slowest_name}), {:.2}x",
+            fastest.summary.median,
+            slowest.summary.median,
+            slowest.nanos() / fastest.nanos().max(1.0)
+        ));
+    }
+
+    lines
+}
+
+/// The output of a day, either printed right away or buffered to be printed later.
+enum Output {
+    Print,
+    Buffer(Vec<Line>),
+}
+
+enum Line {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl Output {
+    fn println(&mut self, line: String) {
+        match self {
+            Output::Print => println!("{line}"),
+            Output::Buffer(lines) => lines.push(Line::Stdout(line)),
+        }
+    }
+
+    fn eprintln(&mut self, line: String) {
+        match self {
+            Output::Print => eprintln!("{line}"),
+            Output::Buffer(lines) => lines.push(Line::Stderr(line)),
+        }
+    }
+
+    /// Prints the buffered lines.
+    fn flush(self) {
+        if let Output::Buffer(lines) = self {
+            for line in lines {
+                match line {
+                    Line::Stdout(line) => println!("{line}"),
+                    Line::Stderr(line) => eprintln!("{line}"),
+                }
+            }
+        }
+    }
+}
+
+/// What a running solution sends to [`collect_reports`].
+enum Event {
+    Report(Report),
+    /// A line printed by the solution on its standard output, e.g. a debug print.
+    Stdout(String),
+    /// A line printed by the solution on its standard error.
+    Stderr(String),
+}
+
+/// Runs the solution of a day from the registry, printing the reports as they would be by the binary.
+///
+/// The solution runs on a worker thread, which is abandoned if a step runs longer than `timeout`.
+/// NOTE: threads cannot be killed, an abandoned worker keeps running until the process exits.
+/// Use `--isolated` to kill the solutions that time out.
+fn run_in_process(
+    day: Day,
+    input: Option<&Path>,
+    is_checked: bool,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    output: &mut Output,
+) -> Vec<Report> {
+    let Some(solution) = solutions::get(day) else {
+        return vec![];
+    };
+
+    let default_path = data_dir().join("inputs").join(format!("{day}.txt"));
+    let input = match fs::read_to_string(input.unwrap_or(&default_path)) {
+        Ok(input) => input,
+        Err(e) => {
+            output.eprintln(format!("Could not read input file: {e}"));
+            return vec![];
+        }
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        (solution.run)(&input, is_timed, &mut |report| {
+            // NOTE: the receiver is gone once the run timed out.
+            let _ = sender.send(Event::Report(report));
+        });
+    });
+
+    collect_reports(&receiver, day, is_checked, solution.steps, timeout, output).0
+}
+
+/// Receives the reports of a solution until it completes, or until one of its steps times out.
+/// The reports and the other lines printed by the solution are written to the output as they come.
+///
+/// When a step times out, a report is added for it and the second value is `true`.
+fn collect_reports(
+    receiver: &Receiver<Event>,
+    day: Day,
+    is_checked: bool,
+    steps: &[Step],
+    timeout: Option<Duration>,
+    output: &mut Output,
+) -> (Vec<Report>, bool) {
+    let mut reports: Vec<Report> = vec![];
+    // NOTE: only reports push the deadline back, a solution printing in a loop times out too.
+    let mut deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        let received = match deadline {
+            Some(deadline) => {
+                receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match received {
+            Ok(Event::Report(report)) => {
+                let check = is_checked.then(|| check_report(&report, day)).flatten();
+                output.println(format_report(&report, check.as_ref()));
+                reports.push(report);
+                deadline = timeout.map(|timeout| Instant::now() + timeout);
+            }
+            Ok(Event::Stdout(line)) => output.println(line),
+            Ok(Event::Stderr(line)) => output.eprintln(line),
+            Err(RecvTimeoutError::Disconnected) => return (reports, false),
+            Err(RecvTimeoutError::Timeout) => {
+                // the step that timed out is the first one that did not report.
+                let step = steps
+                    .iter()
+                    .find(|step| reports.iter().all(|report| report.step != **step));
+
+                if let Some(&step) = step {
+                    let report = Report {
+                        step,
+                        answer: None,
+                        status: Status::TimedOut,
+                        summary: Summary::single(timeout.unwrap_or_default()),
+                        memory: None,
+                        panic: None,
+                    };
+                    output.println(format_report(&report, None));
+                    reports.push(report);
+                }
+
+                return (reports, true);
+            }
+        }
     }
 }
 
+/// Collects the timings of the steps reported by a solution binary.
+///
+/// Only the steps that ran to completion are timed, but the status of every step is recorded.
+pub fn timing_from_reports(reports: &[Report], day: Day) -> Timing {
+    let mut timing = Timing::new(day);
+
+    for report in reports {
+        let is_ok = report.status == Status::Ok;
+        let step = is_ok.then(|| StepTiming {
+            check: check_report(report, day),
+            ..StepTiming::from_summary(report.summary, report.memory)
+        });
+        let status = Some(report.status);
+
+        match report.step {
+            Step::Parse => {
+                timing.parse = step;
+                timing.parse_status = status;
+            }
+            Step::Part(1) => {
+                timing.part_1 = step;
+                timing.part_1_status = status;
+            }
+            Step::Part(2) => {
+                timing.part_2 = step;
+                timing.part_2_status = status;
+            }
+            Step::Part(_) => continue,
+        }
+
+        if is_ok {
+            timing.total_nanos += report.nanos();
+        }
+    }
+
+    timing
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -57,45 +534,82 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, Event, Output};
+    use crate::solutions;
+    use crate::template::{
+        bin_name, get_path_for_bin,
+        input::InputSource,
+        protocol::{Report, JSON_FLAG},
+        Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
+        io::{self, BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::mpsc,
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    ///
+    /// The child process is killed if a step runs longer than `timeout`.
+    pub(super) fn run_solution(
+        day: Day,
+        input: &InputSource,
+        is_checked: bool,
+        is_timed: bool,
+        is_release: bool,
+        timeout: Option<Duration>,
+        output: &mut Output,
+    ) -> Result<Vec<Report>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = bin_name(day);
+        let mut cargo_args = vec!["--quiet", "--bin", &bin_name];
 
         if is_release {
-            args.push("--release");
+            cargo_args.push("--release");
         }
 
+        // build beforehand, so that the compilation does not count toward the timeout.
+        if timeout.is_some() {
+            let build = Command::new("cargo")
+                .arg("build")
+                .args(&cargo_args)
+                .output()?;
+
+            String::from_utf8_lossy(&build.stderr)
+                .lines()
+                .for_each(|line| output.eprintln(line.into()));
+
+            if !build.status.success() {
+                return Ok(vec![]);
+            }
+        }
+
+        let input_args = input.to_args();
+        let mut args = vec!["run"];
+        args.extend(&cargo_args);
+
+        // ask the child for machine-readable reports.
+        args.push("--");
+        args.push(JSON_FLAG);
+        args.extend(input_args.iter().map(String::as_str));
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while collecting the reports.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -106,150 +620,269 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let (sender, receiver) = mpsc::channel();
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stderr_sender = sender.clone();
+        let stderr_thread = thread::spawn(move || {
+            for line in read_lines(stderr) {
+                let event = match line {
+                    Ok(line) => Event::Stderr(line),
+                    Err(e) => {
+                        Event::Stderr(format!("Could not read the output of the solution: {e}"))
+                    }
+                };
+                let _ = stderr_sender.send(event);
+            }
+        });
+
+        // NOTE: the reports and the other lines are sent through the same channel to keep them in order.
+        let stdout_thread = thread::spawn(move || {
+            for line in read_lines(stdout) {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(e) => {
+                        let _ = sender.send(Event::Stderr(format!(
+                            "Could not read the output of the solution: {e}"
+                        )));
+                        break;
+                    }
+                };
+                let event = match Report::from_json_line(&line) {
+                    Some(Ok(report)) => Event::Report(report),
+                    Some(Err(e)) => {
+                        Event::Stderr(format!("Could not read report from line: {line} ({e})"))
+                    }
+                    // not a report, e.g. a debug print from the solution.
+                    None => Event::Stdout(line),
+                };
+                let _ = sender.send(event);
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let steps = solutions::get(day).map_or(&[][..], |solution| solution.steps);
+        let (reports, is_timed_out) =
+            super::collect_reports(&receiver, day, is_checked, steps, timeout, output);
+
+        if is_timed_out {
+            cmd.kill()?;
         }
 
-        thread.join().unwrap();
+        stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(reports)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Reads the lines printed by a solution, replacing invalid UTF-8 rather than failing on it.
+    /// Stops at the first read error, which is yielded last.
+    pub(super) fn read_lines(reader: impl BufRead) -> impl Iterator<Item = io::Result<String>> {
+        let mut is_failed = false;
+        reader.split(b'\n').map_while(move |line| {
+            if is_failed {
+                return None;
+            }
+            is_failed = line.is_err();
+            Some(line.map(|mut line| {
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                String::from_utf8_lossy(&line).into_owned()
+            }))
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::Path, time::Duration};
+
+    use super::{
+        child_commands::read_lines, format_spread, get_input_name, parse_timeout,
+        timing_from_reports, RunOptions,
+    };
+    use crate::{
+        day,
+        template::{
+            protocol::{Report, Status, Step},
+            stats::Summary,
+        },
+    };
+
+    fn report(step: Step, status: Status, micros: u64) -> Report {
+        Report {
+            step,
+            answer: None,
+            status,
+            summary: Summary::single(Duration::from_micros(micros)),
+            memory: None,
+            panic: None,
+        }
+    }
+
+    #[test]
+    fn reads_lines_of_invalid_utf8() {
+        let output = b"Part 1: 42\r\n\xff\xfe oops\n{\"part\": 2}";
+        let lines: Vec<String> = read_lines(&output[..]).map(Result::unwrap).collect();
+        assert_eq!(
+            lines,
+            ["Part 1: 42", "\u{fffd}\u{fffd} oops", "{\"part\": 2}"]
+        );
+    }
+
+    #[test]
+    fn parses_timeouts() {
+        assert_eq!(parse_timeout("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_timeout("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_timeout("2h").is_err(), true);
+        assert_eq!(parse_timeout("s").is_err(), true);
+    }
+
+    #[test]
+    fn runs_timed_days_serially_by_default() {
+        let options = RunOptions {
+            is_timed: true,
+            ..RunOptions::default()
         };
+        assert_eq!(options.jobs(), 1);
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let options = RunOptions {
+            jobs: Some(4),
+            ..options
+        };
+        // NOTE: the allocations of days running in-process at once cannot be told apart.
+        let expected = if cfg!(feature = "alloc-stats") { 1 } else { 4 };
+        assert_eq!(options.jobs(), expected);
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    #[test]
+    fn runs_days_serially_unless_asked() {
+        assert_eq!(RunOptions::default().jobs(), 1);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        let options = RunOptions {
+            is_isolated: true,
+            jobs: Some(4),
+            ..RunOptions::default()
+        };
+        assert_eq!(options.jobs(), 4);
+    }
 
-                timings.total_nanos += nanos;
-            });
+    #[test]
+    fn isolates_timed_runs_with_a_timeout() {
+        let options = RunOptions {
+            timeout: Some(Duration::from_secs(1)),
+            ..RunOptions::default()
+        };
+        assert!(!options.is_isolated());
 
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+        let options = RunOptions {
+            is_timed: true,
+            ..options
+        };
+        assert!(options.is_isolated());
+    }
+
+    #[test]
+    fn collects_timings() {
+        let res = timing_from_reports(
+            &[
+                report(Step::Parse, Status::Ok, 2),
+                report(Step::Part(1), Status::Ok, 74),
+                report(Step::Part(2), Status::Ok, 74_000),
+            ],
+            day!(1),
+        );
+        assert_eq!(res.total_nanos, 74_076_000_f64);
+        let part_1 = res.part_1.unwrap();
+        assert_eq!(res.parse.unwrap().nanos, 2_000_f64);
+        assert_eq!(part_1.nanos, 74_000_f64);
+        assert_eq!(part_1.samples, 1);
+        assert_eq!(part_1.stats.unwrap().median, Duration::from_micros(74));
+        assert_eq!(part_1.check, None);
+        assert_eq!(res.part_2.unwrap().duration(), Duration::from_millis(74));
+    }
+
+    #[test]
+    fn counts_outliers_as_samples() {
+        let report = Report {
+            summary: Summary {
+                samples: 95,
+                outliers: 5,
+                ..Summary::single(Duration::from_micros(74))
+            },
+            ..report(Step::Part(1), Status::Ok, 74)
+        };
+        let res = timing_from_reports(&[report.clone()], day!(1));
+        assert_eq!(res.part_1.unwrap().samples, report.samples());
+        assert_eq!(report.samples(), 100);
+    }
+
+    #[test]
+    fn skips_unsolved_parts() {
+        let res = timing_from_reports(
+            &[
+                report(Step::Part(1), Status::Ok, 10),
+                report(Step::Part(2), Status::Unsolved, 10),
+            ],
+            day!(1),
+        );
+        assert_eq!(res.total_nanos, 10_000_f64);
+        assert_eq!(res.parse.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
+
+    #[test]
+    fn records_panicked_parts() {
+        let res = timing_from_reports(
+            &[
+                report(Step::Part(1), Status::Panicked, 10),
+                report(Step::Part(2), Status::Ok, 10),
+            ],
+            day!(1),
+        );
+        assert_eq!(res.total_nanos, 10_000_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_1_status, Some(Status::Panicked));
+        assert_eq!(res.part_2_status, Some(Status::Ok));
+    }
+
+    #[test]
+    fn names_inputs() {
+        let path = Path::new("data")
+            .join("inputs")
+            .join("01")
+            .join("alice.txt");
+        assert_eq!(get_input_name(&path), "01/alice.txt");
+        assert_eq!(get_input_name(Path::new("other.txt")), "other.txt");
+    }
+
+    #[test]
+    fn formats_spread() {
+        let runs = vec![
+            (
+                "01.txt".to_string(),
+                vec![
+                    report(Step::Part(1), Status::Ok, 10),
+                    report(Step::Part(2), Status::Ok, 40),
                 ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+            ),
+            (
+                "01/alice.txt".to_string(),
+                vec![
+                    report(Step::Part(1), Status::Ok, 25),
+                    report(Step::Part(2), Status::Panicked, 1),
                 ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+            ),
+        ];
+
+        let lines = format_spread(&runs);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].contains("Spread over 2 inputs:"), true);
+        assert_eq!(
+            lines[1],
+            "Part 1: 10.0µs (01.txt) to 25.0µs (01/alice.txt), 2.50x"
+        );
     }
 }
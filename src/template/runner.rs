@@ -1,63 +1,303 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::Once;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers::{self, Check};
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::input::InputSource;
+use crate::template::protocol::{Report, Status, Step, JSON_FLAG};
+use crate::template::readme_stars;
+use crate::template::stats::Summary;
+use crate::template::submissions::{SubmissionOutcome, Submissions};
+use crate::template::{Day, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+    let step = Step::Part(part);
+    let is_json = is_json_output();
+    let is_timed = is_timed_run();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, summary, memory) = run_timed(func, input, is_timed, |result| {
+        if !is_json {
+            print_intermediate_result(result, &step.to_string());
+            print_benching(is_timed);
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    emit_report(&part_report(part, &result, summary, memory), day, is_json);
 
-    if let Some(result) = result {
+    if let Ok(Some(result)) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run the parser of a solution and returns the parsed input.
+/// The parsing is timed the same way as a solution part.
+pub fn run_parse<'a, P>(parse: impl Fn(&'a str) -> P, input: &'a str, day: Day) -> P {
+    let is_json = is_json_output();
+    let is_timed = is_timed_run();
+
+    let (parsed, summary, memory) = run_timed(parse, input, is_timed, |_| {
+        if !is_json {
+            print!("{}:", Step::Parse);
+            print_benching(is_timed);
+        }
+    });
+
+    emit_report(
+        &parse_report(parsed.as_ref().err(), summary, memory),
+        day,
+        is_json,
+    );
+
+    // NOTE: the parts cannot run without the parsed input.
+    parsed.unwrap_or_else(|_| process::exit(1))
+}
+
+/// Run a solution part without printing anything and returns its report.
+///
+/// This is used to run the solutions in-process, see [`crate::solutions`].
+pub fn solve_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    part: u8,
+    is_timed: bool,
+) -> Report {
+    let (result, summary, memory) = run_timed(func, input, is_timed, |_| {});
+    part_report(part, &result, summary, memory)
+}
+
+/// Run the parser of a solution without printing anything and returns the parsed input and its report.
+/// The parsed input is [`None`] if the parser panicked.
+///
+/// This is used to run the solutions in-process, see [`crate::solutions`].
+pub fn solve_parse<'a, P>(
+    parse: impl Fn(&'a str) -> P,
+    input: &'a str,
+    is_timed: bool,
+) -> (Option<P>, Report) {
+    let (parsed, summary, memory) = run_timed(parse, input, is_timed, |_| {});
+    let report = parse_report(parsed.as_ref().err(), summary, memory);
+    (parsed.ok(), report)
+}
+
+fn part_report<T: Display>(
+    part: u8,
+    result: &Result<Option<T>, String>,
+    summary: Summary,
+    memory: Option<AllocStats>,
+) -> Report {
+    Report {
+        step: Step::Part(part),
+        answer: result
+            .as_ref()
+            .ok()
+            .and_then(Option::as_ref)
+            .map(ToString::to_string),
+        status: match result {
+            Ok(Some(_)) => Status::Ok,
+            Ok(None) => Status::Unsolved,
+            Err(_) => Status::Panicked,
+        },
+        summary,
+        memory,
+        panic: result.as_ref().err().cloned(),
+    }
+}
+
+fn parse_report(panic: Option<&String>, summary: Summary, memory: Option<AllocStats>) -> Report {
+    Report {
+        step: Step::Parse,
+        answer: None,
+        status: if panic.is_some() {
+            Status::Panicked
+        } else {
+            Status::Ok
+        },
+        summary,
+        memory,
+        panic: panic.cloned(),
+    }
+}
+
+fn emit_report(report: &Report, day: Day, is_json: bool) {
+    if is_json {
+        // NOTE: the answers are checked by the consumer of the reports.
+        println!("{}", report.to_json_line());
+    } else if InputSource::from_env().is_puzzle() {
+        print_report(report, check_report(report, day).as_ref());
+    } else {
+        // the stored answers are only valid for the puzzle input.
+        print_report(report, None);
+    }
+}
+
+/// Checks the answer of a report against the stored one.
+///
+/// Returns [`None`] if the report has no answer to check.
+pub(crate) fn check_report(report: &Report, day: Day) -> Option<Check> {
+    match (report.step, &report.answer) {
+        (Step::Part(part), Some(answer)) => Some(answers::check_answer(day, part, answer)),
+        _ => None,
+    }
+}
+
+/// Prints a report in a human readable format, replacing the intermediate result of the step.
+fn print_report(report: &Report, check: Option<&Check>) {
+    print!("\r");
+    println!("{}", format_report(report, check));
+}
+
+/// Formats a report in a human readable format.
+pub(crate) fn format_report(report: &Report, check: Option<&Check>) -> String {
+    if report.status == Status::TimedOut {
+        return format!(
+            "{}: ⏱ timed out after {:.1?}",
+            report.step, report.summary.median
+        );
+    }
+
+    if let Some(panic) = &report.panic {
+        return format!("{}: ✖ panicked at {panic}", report.step);
+    }
+
+    let duration_str = format!(
+        "{}{}",
+        format_duration(&report.summary),
+        format_memory(report.memory.as_ref())
+    );
+    match report.step {
+        Step::Parse => format!("{}:{duration_str}", report.step),
+        Step::Part(_) => format_result(
+            &report.answer,
+            &report.step.to_string(),
+            check,
+            &duration_str,
+        ),
+    }
+}
+
+fn print_benching(is_timed: bool) {
+    if is_timed {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    }
+    let _ = stdout().flush();
+}
+
+fn is_json_output() -> bool {
+    env::args().any(|x| x == JSON_FLAG)
+}
+
+fn is_timed_run() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// The heap usage is measured on the first execution, when the `alloc-stats` feature is enabled.
+/// If the first execution panics, the panic is returned as an error and the function is not benched.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
-
-    hook(&result);
+) -> (Result<T, String>, Summary, Option<AllocStats>) {
+    let ((result, base_time), memory) = alloc::measure(|| {
+        let timer = Instant::now();
+        let result = catch_panic(|| func(input.clone()));
+        (result, timer.elapsed())
+    });
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let summary = match &result {
+        Ok(result) if is_timed => {
+            hook(result);
+            bench(func, input, &base_time)
+        }
+        Ok(result) => {
+            hook(result);
+            Summary::single(base_time)
+        }
+        Err(_) => Summary::single(base_time),
     };
 
-    (result, run.0, run.1)
+    (result, summary, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+thread_local! {
+    /// Whether a panic on the current thread is caught by [`catch_panic`].
+    static IS_CATCHING_PANIC: Cell<bool> = const { Cell::new(false) };
+    /// The location of the last panic caught on the current thread.
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Calls the function and catches the panic it may raise.
+/// Returns the location and message of the panic as an error.
+///
+/// NOTE: only unwinding panics can be caught, the solutions must not be compiled with `panic = "abort"`.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static INSTALL_HOOK: Once = Once::new();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    // the location is only known by the panic hook, which also prints the panic by default.
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING_PANIC.get() {
+                PANIC_LOCATION.set(info.location().map(|location| {
+                    // NOTE: in-process solutions are included by absolute path, see `build.rs`.
+                    let file = Path::new(location.file());
+                    let file = file
+                        .strip_prefix(env!("CARGO_MANIFEST_DIR"))
+                        .unwrap_or(file);
+                    format!(
+                        "{}:{}:{}",
+                        file.display(),
+                        location.line(),
+                        location.column()
+                    )
+                }));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    IS_CATCHING_PANIC.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING_PANIC.set(false);
+
+    result.map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".into());
+
+        match PANIC_LOCATION.take() {
+            Some(location) => format!("{location}: {message}"),
+            None => message,
+        }
+    })
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Summary {
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+
+    // warm up caches and branch predictors before taking any measurement.
+    let warmup_iterations = cmp::max(bench_iterations / 10, 1);
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,72 +309,63 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: there is always at least 10 samples.
+    Summary::from_samples(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(summary: &Summary) -> String {
+    if summary.total_samples() == 1 {
+        format!(" ({:.1?})", summary.median)
+    } else {
+        format!(
+            " ({:.1?} @ {} samples) {summary}",
+            summary.median,
+            summary.total_samples()
+        )
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_memory(memory: Option<&AllocStats>) -> String {
+    memory
+        .map(|memory| format!(" [{memory}]"))
+        .unwrap_or_default()
+}
+
+/// Prints the result of a step before it is benched.
+fn print_intermediate_result<T: Display>(result: &Option<T>, part: &str) {
+    match result {
+        Some(result) if result.to_string().contains('\n') => print!("{part}: ▼ "),
+        Some(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+        None => print!("{part}: ✖"),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+fn format_result<T: Display>(
+    result: &Option<T>,
+    part: &str,
+    check: Option<&Check>,
+    duration_str: &str,
+) -> String {
+    let check_str = check.map(|check| format!(" {check}")).unwrap_or_default();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
+        Some(result) if result.to_string().contains('\n') => {
+            format!("{part}: ▼{check_str} {duration_str}\n{result}")
         }
+        Some(result) => format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{check_str}{duration_str}"),
+        // NOTE: the trailing spaces overwrite the intermediate output.
+        None => format!("{part}: ✖             "),
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the session cookie is set, see [`AocClient::from_env`].
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<SubmissionOutcome, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +388,82 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if !InputSource::from_env().is_puzzle() {
+        eprintln!("Refusing to submit: the answer was not computed on the puzzle input.");
+        return None;
+    }
+
+    let client = AocClient::from_env().unwrap_or_else(|e| {
+        eprintln!("{e}");
         process::exit(1);
+    });
+
+    let result = result.to_string();
+    let mut submissions = match Submissions::read_from_file() {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Refusing to submit: {e} Fix or remove it first.");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = submissions.check(day, part, &result) {
+        eprintln!("Refusing to submit {result}: {refusal}");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    let outcome = match client.submit(day, part, &result) {
+        Ok(response) => {
+            let outcome = SubmissionOutcome::from_response(&response);
+            // NOTE: the response is only shown when it does not match any known outcome.
+            if outcome == SubmissionOutcome::Unknown {
+                print!("{response}");
+            }
+            println!("Submitted {result}: {outcome}");
+            outcome
+        }
+        Err(e) => {
+            eprintln!("Failed to submit: {e}");
+            return Some(Err(e));
+        }
+    };
+
+    if submissions.record(day, part, &result, outcome) {
+        if let Err(e) = submissions.store_file() {
+            eprintln!("Failed to store submission: {e}");
+        }
+    }
+
+    match answers::record_answer(day, part, &result, outcome) {
+        Ok(true) => {
+            println!(
+                "🎄 Stored answer to \"{}\".",
+                answers::get_answer_path(day, part).display()
+            );
+            if readme_stars::update().is_err() {
+                eprintln!("Failed to store updated stars.");
+            }
+        }
+        Ok(false) => {}
+        Err(e) => eprintln!("Failed to store answer: {e}"),
+    }
+
+    Some(Ok(outcome))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::catch_panic;
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let panic = catch_panic(|| -> u32 { panic!("no solution") }).unwrap_err();
+        assert_eq!(panic.starts_with("src/template/runner.rs:"), true);
+        assert_eq!(panic.ends_with(": no solution"), true);
+    }
 }
@@ -0,0 +1,18 @@
+use super::{
+    protocol::{Report, Step},
+    Day,
+};
+
+/// A solution compiled in the library, that can be run in-process.
+///
+/// Created by the [`solution!`](crate::solution) macro, see [`crate::solutions`] for the registry.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: Day,
+    /// The steps run by the solution, in order.
+    pub steps: &'static [Step],
+    /// Runs every step of the solution on the provided input and passes their reports to the callback,
+    /// as soon as each step completes.
+    /// Each step is benched if the second argument is `true`.
+    pub run: fn(&str, bool, &mut dyn FnMut(Report)),
+}
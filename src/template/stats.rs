@@ -0,0 +1,180 @@
+/// Module that summarizes benchmark samples.
+use std::fmt::Display;
+use std::time::Duration;
+
+/// Statistical summary of a set of benchmark samples.
+///
+/// Outliers are rejected using Tukey's fences (1.5 × IQR) before the
+/// statistics are computed, so a single preemption doesn't skew the report.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+    /// Number of samples kept after outlier rejection.
+    pub samples: u128,
+    /// Number of samples rejected as outliers.
+    pub outliers: u128,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+}
+
+impl Summary {
+    /// Creates a summary for a single measurement.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            outliers: 0,
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            std_dev: Duration::ZERO,
+            p95: duration,
+        }
+    }
+
+    /// The number of samples taken, including the rejected outliers.
+    pub fn total_samples(&self) -> u128 {
+        self.samples + self.outliers
+    }
+
+    /// Computes the summary of the provided samples.
+    ///
+    /// Returns [`None`] if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25.0);
+        let q3 = percentile(&sorted, 75.0);
+        let iqr = q3.saturating_sub(q1);
+        let low = q1.saturating_sub(iqr * 3 / 2);
+        let high = q3.saturating_add(iqr * 3 / 2);
+
+        let kept: Vec<Duration> = sorted
+            .iter()
+            .copied()
+            .filter(|x| (low..=high).contains(x))
+            .collect();
+
+        // NOTE: `q1` and `q3` are samples, so at least these ones are kept.
+        let n = kept.len() as u128;
+        let mean_nanos = kept.iter().map(Duration::as_nanos).sum::<u128>() / n;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = if n > 1 {
+            kept.iter()
+                .map(|x| {
+                    let delta = x.as_nanos().abs_diff(mean_nanos) as f64;
+                    delta * delta
+                })
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            samples: n,
+            outliers: (sorted.len() - kept.len()) as u128,
+            mean: duration_from_nanos(mean_nanos),
+            median: percentile(&kept, 50.0),
+            min: kept[0],
+            max: kept[kept.len() - 1],
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            std_dev: Duration::from_nanos(variance.sqrt().round() as u64),
+            p95: percentile(&kept, 95.0),
+        })
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[mean {:.1?} · min {:.1?} · max {:.1?} · σ {:.1?} · p95 {:.1?}",
+            self.mean, self.min, self.max, self.std_dev, self.p95
+        )?;
+        if self.outliers > 0 {
+            write!(f, " · {} outliers", self.outliers)?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn duration_from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Summary;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Summary::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let summary = Summary::from_samples(&ms(&[5])).unwrap();
+        assert_eq!(summary, Summary::single(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let summary = Summary::from_samples(&ms(&[4, 1, 3, 2, 5])).unwrap();
+        assert_eq!(summary.samples, 5);
+        assert_eq!(summary.outliers, 0);
+        assert_eq!(summary.mean, Duration::from_millis(3));
+        assert_eq!(summary.median, Duration::from_millis(3));
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.max, Duration::from_millis(5));
+        assert_eq!(summary.p95, Duration::from_millis(5));
+        // sample standard deviation of 1..=5 is sqrt(2.5)
+        assert_eq!(summary.std_dev.as_micros(), 1581);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let summary = Summary::from_samples(&ms(&[10, 11, 10, 12, 11, 10, 350])).unwrap();
+        assert_eq!(summary.samples, 6);
+        assert_eq!(summary.outliers, 1);
+        assert_eq!(summary.max, Duration::from_millis(12));
+        assert_eq!(summary.median, Duration::from_millis(10));
+    }
+
+    #[test]
+    fn displays_summary() {
+        let summary = Summary::from_samples(&ms(&[1, 2, 3])).unwrap();
+        assert_eq!(
+            summary.to_string(),
+            "[mean 2.0ms · min 1.0ms · max 3.0ms · σ 1.0ms · p95 3.0ms]"
+        );
+    }
+}
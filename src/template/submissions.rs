@@ -0,0 +1,602 @@
+/// Module that keeps a log of every submitted answer, to avoid submitting answers that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, Day};
+
+fn get_submissions_path() -> PathBuf {
+    data_dir().join("submissions.json")
+}
+
+/// The verdict of Advent of Code on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// Reads the verdict from the response to a submission.
+    ///
+    /// Returns [`None`] if the answer has not been judged, e.g. when submitting too soon.
+    pub fn from_response(response: &str) -> Option<Self> {
+        if response.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if response.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if response.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if response.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else {
+            None
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// The outcome of a submission, read from the response of Advent of Code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    /// The answer has been judged.
+    Judged(Verdict),
+    /// An answer has been submitted too recently, the next one is accepted after the wait, if known.
+    TooRecent { wait: Option<Duration> },
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    /// The response could not be read.
+    Unknown,
+}
+
+impl SubmissionOutcome {
+    /// Reads the outcome from the response to a submission.
+    pub fn from_response(response: &str) -> Self {
+        if let Some(verdict) = Verdict::from_response(response) {
+            SubmissionOutcome::Judged(verdict)
+        } else if response.contains("You gave an answer too recently") {
+            SubmissionOutcome::TooRecent {
+                wait: parse_wait(response),
+            }
+        } else if response.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::WrongLevel
+        } else {
+            SubmissionOutcome::Unknown
+        }
+    }
+
+    /// The verdict on the answer, if it has been judged.
+    pub fn verdict(self) -> Option<Verdict> {
+        match self {
+            SubmissionOutcome::Judged(verdict) => Some(verdict),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Judged(Verdict::Correct) => write!(f, "✔ the answer is correct."),
+            SubmissionOutcome::Judged(verdict) => write!(f, "✖ the answer is {verdict}."),
+            SubmissionOutcome::TooRecent { wait: Some(wait) } => write!(
+                f,
+                "⏳ an answer was submitted too recently, try again in {}.",
+                format_wait(*wait)
+            ),
+            SubmissionOutcome::TooRecent { wait: None } => {
+                write!(
+                    f,
+                    "⏳ an answer was submitted too recently, try again later."
+                )
+            }
+            SubmissionOutcome::WrongLevel => {
+                write!(f, "✖ this part is already solved, or not unlocked yet.")
+            }
+            SubmissionOutcome::Unknown => write!(f, "? the response could not be read."),
+        }
+    }
+}
+
+/// Reads the wait of a rate-limited submission, e.g. "You have 1m 30s left to wait."
+fn parse_wait(response: &str) -> Option<Duration> {
+    let end = response.find(" left to wait")?;
+    let start = response[..end].rfind("have ")? + "have ".len();
+
+    response[start..end]
+        .split_whitespace()
+        .try_fold(Duration::ZERO, |wait, amount| {
+            let (value, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let unit_secs = match unit {
+                "s" => 1,
+                "m" => 60,
+                "h" => 60 * 60,
+                "d" => 24 * 60 * 60,
+                _ => return None,
+            };
+            Some(wait + Duration::from_secs(value.parse::<u64>().ok()? * unit_secs))
+        })
+}
+
+fn format_wait(wait: Duration) -> String {
+    match wait.as_secs() {
+        secs if secs >= 60 => format!("{}m {}s", secs / 60, secs % 60),
+        secs => format!("{secs}s"),
+    }
+}
+
+/// A submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the UNIX epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Submission {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp,
+        }
+    }
+}
+
+/// The reason why an answer should not be submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The very same answer has already been rejected.
+    AlreadyRejected(Verdict),
+    /// A lower answer has already been judged too high.
+    TooHigh { bound: String },
+    /// A higher answer has already been judged too low.
+    TooLow { bound: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected(verdict) => {
+                write!(f, "this answer has already been submitted ({verdict}).")
+            }
+            Refusal::TooHigh { bound } => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which is too high."
+                )
+            }
+            Refusal::TooLow { bound } => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which is too low."
+                )
+            }
+        }
+    }
+}
+
+/// Log of every submitted answer.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(data_dir())?;
+        let mut file = fs::File::create(get_submissions_path())?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    ///
+    /// Errors if the file can't be read or parsed: storing an empty log in its place would lose the history.
+    pub fn read_from_file() -> Result<Self, String> {
+        let path = get_submissions_path();
+        let s = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Submissions::default()),
+            Err(e) => return Err(format!("could not read \"{}\": {e}.", path.display())),
+        };
+
+        Submissions::try_from(s).map_err(|e| format!("\"{}\" is malformed: {e}", path.display()))
+    }
+
+    /// Records a submitted answer if it has been judged, returns whether it has been.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, outcome: SubmissionOutcome) -> bool {
+        let Some(verdict) = outcome.verdict() else {
+            return false;
+        };
+        self.data.push(Submission::new(day, part, answer, verdict));
+        true
+    }
+
+    /// Checks whether an answer is worth submitting, given the previous submissions of the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let previous = self.data.iter().filter(|s| s.day == day && s.part == part);
+
+        let value = answer.trim().parse::<i128>().ok();
+
+        for submission in previous {
+            if submission.answer == answer && submission.verdict != Verdict::Correct {
+                return Err(Refusal::AlreadyRejected(submission.verdict));
+            }
+
+            let (Some(value), Ok(bound)) = (value, submission.answer.trim().parse::<i128>()) else {
+                continue;
+            };
+
+            match submission.verdict {
+                Verdict::TooHigh if value >= bound => {
+                    return Err(Refusal::TooHigh {
+                        bound: submission.answer.clone(),
+                    })
+                }
+                Verdict::TooLow if value <= bound => {
+                    return Err(Refusal::TooLow {
+                        bound: submission.answer.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .filter(|x| matches!(x, 1 | 2))
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Refusal, Submission, SubmissionOutcome, Submissions, Verdict};
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: 1_701_388_800,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission(1, "100", Verdict::TooHigh),
+                submission(1, "20", Verdict::TooLow),
+                submission(1, "50", Verdict::Wrong),
+                submission(2, "abc", Verdict::Wrong),
+            ],
+        }
+    }
+
+    mod check {
+        use crate::day;
+
+        use super::{get_mock_submissions, Refusal, Verdict};
+
+        #[test]
+        fn accepts_new_answers() {
+            let submissions = get_mock_submissions();
+            assert_eq!(submissions.check(day!(1), 1, "42"), Ok(()));
+            assert_eq!(submissions.check(day!(1), 2, "abd"), Ok(()));
+            assert_eq!(submissions.check(day!(2), 1, "100"), Ok(()));
+        }
+
+        #[test]
+        fn refuses_rejected_answers() {
+            let submissions = get_mock_submissions();
+            assert_eq!(
+                submissions.check(day!(1), 1, "50"),
+                Err(Refusal::AlreadyRejected(Verdict::Wrong))
+            );
+            assert_eq!(
+                submissions.check(day!(1), 2, "abc"),
+                Err(Refusal::AlreadyRejected(Verdict::Wrong))
+            );
+        }
+
+        #[test]
+        fn refuses_answers_out_of_bounds() {
+            let submissions = get_mock_submissions();
+            assert_eq!(
+                submissions.check(day!(1), 1, "150"),
+                Err(Refusal::TooHigh {
+                    bound: "100".into()
+                })
+            );
+            assert_eq!(
+                submissions.check(day!(1), 1, "10"),
+                Err(Refusal::TooLow { bound: "20".into() })
+            );
+        }
+    }
+
+    mod verdict {
+        use super::Verdict;
+
+        #[test]
+        fn reads_responses() {
+            assert_eq!(
+                Verdict::from_response("That's the right answer! You are one gold star closer."),
+                Some(Verdict::Correct)
+            );
+            assert_eq!(
+                Verdict::from_response("That's not the right answer; your answer is too high."),
+                Some(Verdict::TooHigh)
+            );
+            assert_eq!(
+                Verdict::from_response("That's not the right answer; your answer is too low."),
+                Some(Verdict::TooLow)
+            );
+            assert_eq!(
+                Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+                Some(Verdict::Wrong)
+            );
+            assert_eq!(
+                Verdict::from_response(
+                    "You gave an answer too recently; you have 42s left to wait."
+                ),
+                None
+            );
+        }
+    }
+
+    mod outcome {
+        use std::time::Duration;
+
+        use crate::day;
+
+        use super::{SubmissionOutcome, Submissions, Verdict};
+
+        #[test]
+        fn reads_responses() {
+            assert_eq!(
+                SubmissionOutcome::from_response(
+                    "That's not the right answer; your answer is too low."
+                ),
+                SubmissionOutcome::Judged(Verdict::TooLow)
+            );
+            assert_eq!(
+                SubmissionOutcome::from_response(
+                    "You don't seem to be solving the right level.  Did you already complete it?"
+                ),
+                SubmissionOutcome::WrongLevel
+            );
+            assert_eq!(
+                SubmissionOutcome::from_response("<html>Oops</html>"),
+                SubmissionOutcome::Unknown
+            );
+        }
+
+        #[test]
+        fn reads_wait_times() {
+            assert_eq!(
+                SubmissionOutcome::from_response(
+                    "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 30s left to wait."
+                ),
+                SubmissionOutcome::TooRecent {
+                    wait: Some(Duration::from_secs(90))
+                }
+            );
+            assert_eq!(
+                SubmissionOutcome::from_response(
+                    "You gave an answer too recently; you have 42s left to wait."
+                ),
+                SubmissionOutcome::TooRecent {
+                    wait: Some(Duration::from_secs(42))
+                }
+            );
+            assert_eq!(
+                SubmissionOutcome::from_response("You gave an answer too recently."),
+                SubmissionOutcome::TooRecent { wait: None }
+            );
+        }
+
+        #[test]
+        fn records_judged_answers_only() {
+            let mut submissions = Submissions::default();
+
+            let outcome = SubmissionOutcome::TooRecent { wait: None };
+            assert!(!submissions.record(day!(1), 1, "10", outcome));
+
+            let outcome = SubmissionOutcome::Judged(Verdict::TooHigh);
+            assert!(submissions.record(day!(1), 1, "20", outcome));
+
+            assert_eq!(submissions.data.len(), 1);
+            assert_eq!(submissions.data[0].verdict, Verdict::TooHigh);
+        }
+    }
+
+    mod serialization {
+        use super::{get_mock_submissions, Submissions};
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn round_trips_submissions() {
+            let submissions = get_mock_submissions();
+            let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+            let parsed = Submissions::try_from(json).unwrap();
+            assert_eq!(parsed.data, submissions.data);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_submissions() {
+            let json = r#"{ "data": [{ "day": "01", "part": 3 }] }"#.to_string();
+            Submissions::try_from(json).unwrap();
+        }
+    }
+}
@@ -1,41 +1,161 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    alloc::AllocStats,
+    answers::Check,
+    data_dir,
+    history::Measurement,
+    protocol::{Status, Step},
+    stats::Summary,
+    Day,
+};
+
+fn get_timings_path() -> PathBuf {
+    data_dir().join("timings.json")
+}
+
+/// The version of the format of `timings.json`, bumped whenever it changes.
+///
+/// - `1` (implicit, the file has no `version` key): durations are display strings such as `"74.1ns"`.
+/// - `2`: durations are numbers of nanoseconds, grouped with the statistics of their step.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Benchmark of a single step of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepTiming {
+    /// The median duration of the step, in nanoseconds.
+    pub nanos: f64,
+    /// The number of samples taken, including the rejected outliers, `0` if unknown.
+    pub samples: u128,
+    /// The statistics of the samples, missing from timings migrated from the first schema.
+    pub stats: Option<Summary>,
+    /// NOTE: heap usage is only measured with the `alloc-stats` feature.
+    pub memory: Option<AllocStats>,
+    /// The check of the answer against the accepted one, parts only.
+    pub check: Option<Check>,
+}
+
+impl StepTiming {
+    /// The timing of a step that was measured once.
+    pub fn from_duration(duration: Duration) -> Self {
+        Self::from_summary(Summary::single(duration), None)
+    }
+
+    pub fn from_summary(summary: Summary, memory: Option<AllocStats>) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = summary.median.as_nanos() as f64;
+        Self {
+            nanos,
+            samples: summary.total_samples(),
+            stats: Some(summary),
+            memory,
+            check: None,
+        }
+    }
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+    /// The median duration of the step.
+    pub fn duration(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.nanos as u64)
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<StepTiming>,
+    pub part_1: Option<StepTiming>,
+    pub part_2: Option<StepTiming>,
+    pub parse_status: Option<Status>,
+    pub part_1_status: Option<Status>,
+    pub part_2_status: Option<Status>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// The timing of a day whose steps have not been benched.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_status: None,
+            part_1_status: None,
+            part_2_status: None,
+            total_nanos: 0_f64,
+        }
+    }
+
+    /// The timing of a step, if it has been benched.
+    pub fn step(&self, step: Step) -> Option<&StepTiming> {
+        match step {
+            Step::Parse => self.parse.as_ref(),
+            Step::Part(1) => self.part_1.as_ref(),
+            Step::Part(2) => self.part_2.as_ref(),
+            Step::Part(_) => None,
+        }
+    }
+
+    /// The status of a step, if it has been run.
+    pub fn status(&self, step: Step) -> Option<Status> {
+        match step {
+            Step::Parse => self.parse_status,
+            Step::Part(1) => self.part_1_status,
+            Step::Part(2) => self.part_2_status,
+            Step::Part(_) => None,
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
+///
+/// The keys added after the first schema (the history, the statuses, the statistics,
+/// the heap usage and the checks) are optional: timings stored before they existed don't have them.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// The latest timing of each day.
     pub data: Vec<Timing>,
+    /// Every measurement taken so far, oldest first.
+    pub history: Vec<Measurement>,
 }
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
+        self.store_file_at(get_timings_path())
+    }
+
+    /// Dehydrate timings to a JSON file at the given path.
+    pub fn store_file_at(&self, path: impl AsRef<Path>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings from a JSON file at the given path.
+    pub fn read_from_path(path: impl AsRef<Path>) -> Result<Self, String> {
+        fs::read_to_string(path)
             .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
+            .and_then(Timings::try_from)
+    }
 
-        match s {
+    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file() -> Self {
+        match Self::read_from_path(get_timings_path()) {
             Ok(timings) => timings,
             Err(e) => {
                 eprintln!("{}", e);
@@ -45,6 +165,7 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// The history of `other` is appended to the history of `self`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -59,7 +180,10 @@ impl Timings {
         }
 
         data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+
+        let history = self.history.iter().chain(&new.history).cloned().collect();
+
+        Timings { data, history }
     }
 
     /// Sum up total duration of timings as millis.
@@ -86,11 +210,19 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("version".into(), JsonValue::Number(SCHEMA_VERSION as f64));
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -101,18 +233,51 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: the first schema had no version.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            Some(version) => version
+                .get::<f64>()
+                .map(|x| *x as u64)
+                .ok_or("expected `json.version` to be a number.")?,
+            None => 1,
+        };
+
+        if version > SCHEMA_VERSION {
+            return Err(format!(
+                "timings were stored with schema version {version}, but only versions up to {SCHEMA_VERSION} are supported."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let json_history = match json.get("history") {
+            Some(history) => history
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .as_slice(),
+            None => &[],
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|timing| match version {
+                    1 => Timing::migrate_from_v1(timing),
+                    _ => Timing::try_from(timing),
+                })
+                .collect::<Result<_, _>>()?,
+            history: json_history
+                .iter()
+                .map(Measurement::try_from)
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -127,24 +292,28 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let step =
+            |step: &Option<StepTiming>| step.as_ref().map_or(JsonValue::Null, JsonValue::from);
 
-        map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        map.insert("part_1".into(), step(&value.part_1));
+        map.insert("part_2".into(), step(&value.part_2));
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        // NOTE: only days with a dedicated parser have a parse timing.
+        if value.parse.is_some() {
+            map.insert("parse".into(), step(&value.parse));
+        }
+
+        let statuses = [
+            ("parse_status", value.parse_status),
+            ("part_1_status", value.part_1_status),
+            ("part_2_status", value.part_2_status),
+        ];
+
+        for (key, status) in statuses {
+            if let Some(status) = status {
+                map.insert(key.into(), JsonValue::String(status.as_str().into()));
+            }
+        }
 
         JsonValue::Object(map)
     }
@@ -158,32 +327,323 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+        let step = |key: &str| {
+            json.get(key)
+                .filter(|v| !v.is_null())
+                .map(|v| StepTiming::try_from(v).map_err(|e| format!("timing.{key}: {e}")))
+                .transpose()
+        };
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        for key in ["part_1", "part_2"] {
+            if !json.contains_key(key) {
+                return Err(format!("Expected timing.{key} to be null or an object."));
+            }
+        }
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        Ok(Timing {
+            day: parse_day(json)?,
+            parse: step("parse")?,
+            part_1: step("part_1")?,
+            part_2: step("part_2")?,
+            parse_status: parse_status(json, "parse_status")?,
+            part_1_status: parse_status(json, "part_1_status")?,
+            part_2_status: parse_status(json, "part_2_status")?,
+            total_nanos: parse_total_nanos(json)?,
+        })
+    }
+}
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+impl Timing {
+    /// Reads a timing stored with the first schema, where durations are display strings.
+    fn migrate_from_v1(value: &JsonValue) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .filter(|v| !v.is_null())
+                .map(|v| {
+                    v.get::<String>()
+                        .ok_or(format!("Expected timing.{key} to be null or string."))
+                })
+                .transpose()
+        };
+
+        let step = |key: &str| -> Result<Option<StepTiming>, String> {
+            let stats = json
+                .get(&format!("{key}_stats"))
+                .filter(|v| !v.is_null())
+                .map(Summary::try_from)
+                .transpose()?;
+
+            let memory = json
+                .get(&format!("{key}_memory"))
+                .filter(|v| !v.is_null())
+                .map(AllocStats::try_from)
+                .transpose()?;
+
+            if let Some(stats) = stats {
+                return Ok(Some(StepTiming::from_summary(stats, memory)));
+            }
+
+            string(key)?
+                .map(|duration| {
+                    let nanos = parse_legacy_duration(duration)
+                        .ok_or(format!("Expected timing.{key} to be a duration."))?;
+                    Ok(StepTiming {
+                        nanos,
+                        samples: 0,
+                        stats: None,
+                        memory,
+                        check: None,
+                    })
+                })
+                .transpose()
+        };
+
+        for key in ["part_1", "part_2"] {
+            if !json.contains_key(key) {
+                return Err(format!("Expected timing.{key} to be null or string."));
+            }
+        }
 
         Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            day: parse_day(json)?,
+            parse: step("parse")?,
+            part_1: step("part_1")?,
+            part_2: step("part_2")?,
+            parse_status: parse_status(json, "parse_status")?,
+            part_1_status: parse_status(json, "part_1_status")?,
+            part_2_status: parse_status(json, "part_2_status")?,
+            total_nanos: parse_total_nanos(json)?,
+        })
+    }
+}
+
+fn parse_day(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    json.get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.".into())
+}
+
+fn parse_total_nanos(json: &HashMap<String, JsonValue>) -> Result<f64, String> {
+    json.get("total_nanos")
+        .and_then(|v| v.get::<f64>().copied())
+        .ok_or("Expected timing.total_nanos to be a number.".into())
+}
+
+fn parse_status(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<Status>, String> {
+    json.get(key)
+        .filter(|v| !v.is_null())
+        .map(|v| {
+            v.get::<String>()
+                .ok_or(format!("Expected timing.{key} to be a string."))?
+                .parse::<Status>()
+        })
+        .transpose()
+}
+
+/// Parses a duration formatted with `{:?}`, such as `74.1ns` or `1.5ms`, to nanoseconds.
+fn parse_legacy_duration(s: &str) -> Option<f64> {
+    const UNITS: [(&str, f64); 6] = [
+        ("ns", 1.0),
+        ("µs", 1e3),
+        ("μs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ];
+
+    let s = s.trim();
+    UNITS.iter().find_map(|(unit, factor)| {
+        let value = s.strip_suffix(unit)?.trim().parse::<f64>().ok()?;
+        Some(value * factor)
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&StepTiming> for JsonValue {
+    fn from(value: &StepTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        if let Some(stats) = &value.stats {
+            map.insert("stats".into(), JsonValue::from(stats));
+        }
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        let check = |s: &str| JsonValue::String(s.into());
+        match &value.check {
+            Some(Check::Match) => {
+                map.insert("check".into(), check("match"));
+            }
+            Some(Check::Mismatch { expected }) => {
+                map.insert("check".into(), check("mismatch"));
+                map.insert("expected".into(), JsonValue::String(expected.clone()));
+            }
+            Some(Check::Unknown) => {
+                map.insert("check".into(), check("unknown"));
+            }
+            None => {}
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for StepTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected step timing to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected step timing.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u128)
+            .ok_or("Expected step timing.samples to be a number.")?;
+
+        let stats = json
+            .get("stats")
+            .filter(|v| !v.is_null())
+            .map(Summary::try_from)
+            .transpose()?;
+
+        let memory = json
+            .get("memory")
+            .filter(|v| !v.is_null())
+            .map(AllocStats::try_from)
+            .transpose()?;
+
+        let check = match json.get("check").and_then(|v| v.get::<String>()) {
+            None => None,
+            Some(check) => Some(match check.as_str() {
+                "match" => Check::Match,
+                "unknown" => Check::Unknown,
+                "mismatch" => Check::Mismatch {
+                    expected: json
+                        .get("expected")
+                        .and_then(|v| v.get::<String>())
+                        .cloned()
+                        .ok_or("Expected step timing.expected to be a string.")?,
+                },
+                _ => return Err(format!("Unknown step timing.check `{check}`.")),
+            }),
+        };
+
+        Ok(StepTiming {
+            nanos,
+            samples,
+            stats,
+            memory,
+            check,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Summary> for JsonValue {
+    fn from(value: &Summary) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let number = |x: u128| JsonValue::Number(x as f64);
+
+        map.insert("samples".into(), number(value.samples));
+        map.insert("outliers".into(), number(value.outliers));
+        map.insert("mean_nanos".into(), number(value.mean.as_nanos()));
+        map.insert("median_nanos".into(), number(value.median.as_nanos()));
+        map.insert("min_nanos".into(), number(value.min.as_nanos()));
+        map.insert("max_nanos".into(), number(value.max.as_nanos()));
+        map.insert("std_dev_nanos".into(), number(value.std_dev.as_nanos()));
+        map.insert("p95_nanos".into(), number(value.p95.as_nanos()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Summary {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        Ok(Summary {
+            samples: number("samples")?.into(),
+            outliers: number("outliers")?.into(),
+            mean: Duration::from_nanos(number("mean_nanos")?),
+            median: Duration::from_nanos(number("median_nanos")?),
+            min: Duration::from_nanos(number("min_nanos")?),
+            max: Duration::from_nanos(number("max_nanos")?),
+            std_dev: Duration::from_nanos(number("std_dev_nanos")?),
+            p95: Duration::from_nanos(number("p95_nanos")?),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let number = |x: usize| JsonValue::Number(x as f64);
+
+        map.insert("peak_bytes".into(), number(value.peak_bytes));
+        map.insert("allocations".into(), number(value.allocations));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as usize)
+                .ok_or(format!("Expected memory stats.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
         })
     }
 }
@@ -192,37 +652,97 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{StepTiming, Timing, Timings};
+
+    fn step(millis: u64) -> Option<StepTiming> {
+        Some(StepTiming::from_duration(Duration::from_millis(millis)))
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: step(10),
+                    part_2: step(20),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: step(30),
+                    part_2: step(40),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1: step(40),
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
+            history: vec![],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{answers::Check, timings::Timings},
+        };
+
+        use super::get_mock_timings;
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn round_trips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert_eq!(json.contains(r#""version":2"#), true);
+
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
+
+        #[test]
+        fn handles_json_step_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1500, "samples": 9 }, "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1500_f64);
+            assert_eq!(part_1.samples, 9);
+            assert_eq!(part_1.stats, None);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn round_trips_checks() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1.as_mut().unwrap().check = Some(Check::Match);
+            timings.data[0].part_2.as_mut().unwrap().check = Some(Check::Mismatch {
+                expected: "42".into(),
+            });
+
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[0].part_2, timings.data[0].part_2);
+            assert_eq!(parsed.data[1].part_1.as_ref().unwrap().check, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_schema_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
 
         #[test]
         fn handles_json_timings() {
@@ -231,11 +751,33 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1.as_ref().unwrap().nanos, 1_000_000_f64);
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timing_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0µs", "part_2": null, "total_nanos": 1000, "part_1_stats": { "samples": 9, "outliers": 1, "mean_nanos": 1100, "median_nanos": 1000, "min_nanos": 900, "max_nanos": 1500, "std_dev_nanos": 40, "p95_nanos": 1400 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.as_ref().unwrap().stats.unwrap();
+            assert_eq!(stats.samples, 9);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(stats.median, Duration::from_nanos(1000));
+            assert_eq!(stats.p95, Duration::from_nanos(1400));
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn handles_json_parse_timing() {
+            let json = r#"{ "data": [{ "day": "02", "parse": "1.5µs", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3001500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.as_ref().unwrap().nanos, 1500_f64);
+            assert_eq!(timing.parse.as_ref().unwrap().stats, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -258,6 +800,69 @@ mod tests {
         }
     }
 
+    mod migration {
+        use crate::template::{
+            alloc::AllocStats,
+            timings::{parse_legacy_duration, Timings},
+        };
+
+        #[test]
+        fn parses_legacy_durations() {
+            assert_eq!(parse_legacy_duration("74ns"), Some(74_f64));
+            assert_eq!(parse_legacy_duration("1.5µs"), Some(1500_f64));
+            assert_eq!(parse_legacy_duration("2.0ms"), Some(2_000_000_f64));
+            assert_eq!(parse_legacy_duration("1.2s"), Some(1_200_000_000_f64));
+            assert_eq!(parse_legacy_duration("fast"), None);
+        }
+
+        #[test]
+        fn migrates_string_durations() {
+            let json = r#"{ "data": [{ "day": "03", "parse": "250ns", "part_1": "74.1µs", "part_2": null, "total_nanos": 74350 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.as_ref().unwrap().nanos, 250_f64);
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos.round(), 74_100_f64);
+            assert_eq!(part_1.samples, 0);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn migrates_stats_and_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0µs", "part_2": null, "total_nanos": 1000, "part_1_stats": { "samples": 9, "outliers": 1, "mean_nanos": 1100, "median_nanos": 1020, "min_nanos": 900, "max_nanos": 1500, "std_dev_nanos": 40, "p95_nanos": 1400 }, "part_1_memory": { "peak_bytes": 64, "allocations": 2 }, "part_1_status": "ok" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1020_f64);
+            assert_eq!(part_1.samples, 10);
+            assert_eq!(
+                part_1.memory,
+                Some(AllocStats {
+                    peak_bytes: 64,
+                    allocations: 2
+                })
+            );
+        }
+
+        #[test]
+        fn stores_migrated_timings_with_the_current_schema() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let json = tinyjson::JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(
+                timings.data[0].part_2.as_ref().unwrap().nanos,
+                2_000_000_f64
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "soon", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
         use std::collections::HashMap;
@@ -282,6 +887,7 @@ mod tests {
     }
 
     mod is_day_complete {
+        use super::step;
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -291,11 +897,12 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: step(1),
+                    part_2: step(2),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), true);
@@ -305,11 +912,11 @@ mod tests {
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1: step(1),
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
+                history: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), false);
@@ -318,12 +925,8 @@ mod tests {
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
+                history: vec![],
             };
 
             assert_eq!(timings.is_day_complete(&day!(1)), false);
@@ -331,9 +934,15 @@ mod tests {
     }
 
     mod merge {
+        use tinyjson::JsonValue;
+
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                history::Measurement,
+                protocol::Step,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -342,12 +951,8 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -362,12 +967,8 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -393,5 +994,29 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn appends_history() {
+            let measurement = |timestamp| Measurement {
+                day: day!(1),
+                step: Step::Part(1),
+                timestamp,
+                commit: None,
+                nanos: 1000.0,
+            };
+            let timings = Timings {
+                history: vec![measurement(1)],
+                ..get_mock_timings()
+            };
+            let other = Timings {
+                history: vec![measurement(2)],
+                ..Timings::default()
+            };
+            let merged = timings.merge(&other);
+            assert_eq!(merged.history, [measurement(1), measurement(2)]);
+
+            let json = JsonValue::from(merged.clone()).stringify().unwrap();
+            assert_eq!(Timings::try_from(json).unwrap().history, merged.history);
+        }
     }
 }
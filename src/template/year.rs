@@ -0,0 +1,248 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::Day;
+
+/// The variable that selects the year the commands work on, set by the `--year` flag.
+/// Child processes inherit it, so the solutions read the inputs of the same year.
+const SELECTED_YEAR_ENV: &str = "AOC_SELECTED_YEAR";
+
+/// A year of advent (i.e. an integer from 2015 on).
+///
+/// The repository holds a default year, set by `AOC_YEAR` in `.cargo/config.toml`, and any number of other years:
+///  - the data of the default year is stored in `data/`, the data of the other years in `data/<year>/`.
+///  - the solutions of the default year are `src/bin/<day>.rs`, the solutions of the other years
+///    are `src/bin/<year>-<day>.rs`.
+///  - the tables of the default year are in `README.md`, the tables of the other years in `README-<year>.md`.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2022).unwrap();
+/// assert_eq!(year.to_string(), "2022")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The default year, set by `AOC_YEAR`.
+    pub fn default_year() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// The year selected with `--year`, or the default year.
+    pub fn current() -> Option<Self> {
+        let selected = env::var(SELECTED_YEAR_ENV)
+            .ok()
+            .and_then(|x| x.parse().ok());
+        selected.or_else(Self::default_year)
+    }
+
+    /// Selects the year the commands work on, for this process and its children.
+    pub fn select(self) {
+        env::set_var(SELECTED_YEAR_ENV, self.to_string());
+    }
+
+    // Not part of the public API
+    // NOTE: lets a solution binary of another year, e.g. `2022-01`, run on its own inputs when started directly.
+    #[doc(hidden)]
+    pub fn __select_bin(bin_name: Option<&str>) {
+        if env::var_os(SELECTED_YEAR_ENV).is_some() {
+            return;
+        }
+        let year = bin_name
+            .and_then(|name| name.split_once('-'))
+            .and_then(|(year, _)| year.parse::<Year>().ok());
+        if let Some(year) = year {
+            year.select();
+        }
+    }
+
+    /// Whether the year is the default one, `false` if no default year is configured.
+    pub fn is_default(self) -> bool {
+        Self::default_year() == Some(self)
+    }
+
+    /// The directory of the data of the year, e.g. `data/2022`.
+    pub fn data_dir(self) -> PathBuf {
+        if self.is_default() {
+            PathBuf::from("data")
+        } else {
+            Path::new("data").join(self.to_string())
+        }
+    }
+
+    /// The name of the solution binary of a day, e.g. `2022-01`.
+    pub fn bin_name(self, day: Day) -> String {
+        if self.is_default() {
+            day.to_string()
+        } else {
+            format!("{self}-{day}")
+        }
+    }
+
+    /// The readme that holds the tables of the year, e.g. `README-2022.md`.
+    pub fn readme_path(self) -> PathBuf {
+        if self.is_default() {
+            PathBuf::from("README.md")
+        } else {
+            PathBuf::from(format!("README-{self}.md"))
+        }
+    }
+
+    /// Reads a text file of the year to a string, e.g. `data/2022/examples/01.txt`.
+    #[must_use]
+    pub fn read_file(self, folder: &str, day: Day) -> String {
+        let filepath = self.data_dir().join(folder).join(format!("{day}.txt"));
+        fs::read_to_string(filepath).expect("could not open input file")
+    }
+
+    /// Reads a text file of the year to a string, appending a part suffix. E.g. `data/2022/examples/01-2.txt`.
+    #[must_use]
+    pub fn read_file_part(self, folder: &str, day: Day, part: u8) -> String {
+        let filepath = self
+            .data_dir()
+            .join(folder)
+            .join(format!("{day}-{part}.txt"));
+        fs::read_to_string(filepath).expect("could not open input file")
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year from 2015 on")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The directory of the data of the current year, see [`Year::data_dir`].
+pub fn data_dir() -> PathBuf {
+    Year::current().map_or_else(|| PathBuf::from("data"), Year::data_dir)
+}
+
+/// The name of the solution binary of a day of the current year, see [`Year::bin_name`].
+pub fn bin_name(day: Day) -> String {
+    Year::current().map_or_else(|| day.to_string(), |year| year.bin_name(day))
+}
+
+/// The source file of the solution of a day of the current year, e.g. `./src/bin/2022-01.rs`.
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(day))
+}
+
+/// The readme of the current year, see [`Year::readme_path`].
+pub fn readme_path() -> PathBuf {
+    Year::current().map_or_else(|| PathBuf::from("README.md"), Year::readme_path)
+}
+
+/// Reads the readme of the current year.
+/// The readme of a year other than the default one is created as needed, with an empty table for the marker.
+pub(crate) fn read_readme(marker: &str) -> io::Result<String> {
+    let path = readme_path();
+    let Some(year) = Year::current().filter(|year| !year.is_default()) else {
+        return Ok(String::from_utf8_lossy(&fs::read(path)?).to_string());
+    };
+
+    let mut readme = match fs::read(&path) {
+        Ok(readme) => String::from_utf8_lossy(&readme).to_string(),
+        Err(_) => format!("# Advent of Code {year}\n"),
+    };
+    if !readme.contains(marker) {
+        readme.push_str(&format!("\n{marker}\n{marker}\n"));
+    }
+    Ok(readme)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting a year from 2015 on"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::Year;
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2022".parse::<Year>().ok(), Year::new(2022));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("22".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn qualifies_other_years() {
+        // NOTE: `AOC_YEAR` is set to 2023 in `.cargo/config.toml`.
+        let default = year!(2023);
+        assert_eq!(default.data_dir(), Path::new("data"));
+        assert_eq!(default.bin_name(day!(1)), "01");
+        assert_eq!(default.readme_path(), Path::new("README.md"));
+
+        let other = year!(2022);
+        assert_eq!(other.data_dir(), Path::new("data/2022"));
+        assert_eq!(other.bin_name(day!(1)), "2022-01");
+        assert_eq!(other.readme_path(), Path::new("README-2022.md"));
+    }
+}
@@ -0,0 +1,143 @@
+//! Runs the Advent of Code client against a mock server on localhost.
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use advent_of_code::{
+    day,
+    template::aoc_client::{AocClient, AocClientError},
+};
+
+/// A request received by the mock server.
+struct Request {
+    head: String,
+    body: String,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.head.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+}
+
+/// Serves a single response, and sends the request it answered.
+fn serve(status: u16, body: &'static str) -> (String, Receiver<Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut head = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            head.push_str(&line);
+        }
+
+        let mut request = Request {
+            head,
+            body: String::new(),
+        };
+        let length: usize = request
+            .header("content-length")
+            .map_or(0, |length| length.parse().unwrap());
+        let mut request_body = vec![0; length];
+        reader.read_exact(&mut request_body).unwrap();
+        request.body = String::from_utf8(request_body).unwrap();
+
+        let response = format!(
+            "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        reader.get_mut().write_all(response.as_bytes()).unwrap();
+        sender.send(request).unwrap();
+    });
+
+    (base_url, receiver)
+}
+
+#[test]
+fn downloads_inputs() {
+    let (base_url, requests) = serve(200, "1abc2\npqr3stu8vwx\n");
+    let client = AocClient::new(&base_url, "secret\n", 2023);
+
+    assert_eq!(client.get_input(day!(1)).unwrap(), "1abc2\npqr3stu8vwx\n");
+
+    let request = requests.recv().unwrap();
+    assert!(request.head.starts_with("GET /2023/day/1/input "));
+    assert_eq!(request.header("cookie"), Some("session=secret"));
+}
+
+#[test]
+fn downloads_puzzles_as_markdown() {
+    let (base_url, requests) = serve(
+        200,
+        "<main><article class=\"day-desc\"><h2>--- Day 12: Hot Springs ---</h2><p>You finally reach the <em>hot springs</em>!</p></article></main>",
+    );
+    let client = AocClient::new(&base_url, "secret", 2023);
+
+    assert_eq!(
+        client.get_puzzle(day!(12)).unwrap(),
+        "## --- Day 12: Hot Springs ---\n\nYou finally reach the *hot springs*!\n"
+    );
+    let request = requests.recv().unwrap();
+    assert!(request.head.starts_with("GET /2023/day/12 "));
+    assert!(request
+        .header("user-agent")
+        .is_some_and(|user_agent| user_agent.starts_with("advent_of_code/")));
+}
+
+#[test]
+fn identifies_the_user() {
+    let (base_url, requests) = serve(200, "1abc2\n");
+    let client = AocClient::new(&base_url, "secret", 2023)
+        .with_user_agent("github.com/someone/aoc by someone@example.com");
+
+    client.get_input(day!(1)).unwrap();
+
+    let request = requests.recv().unwrap();
+    assert_eq!(
+        request.header("user-agent"),
+        Some("github.com/someone/aoc by someone@example.com")
+    );
+}
+
+#[test]
+fn submits_answers() {
+    let (base_url, requests) = serve(
+        200,
+        "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+    );
+    let client = AocClient::new(&base_url, "secret", 2023);
+
+    let response = client.submit(day!(3), 2, "4361").unwrap();
+    assert!(response.starts_with("That's the right answer!"));
+
+    let request = requests.recv().unwrap();
+    assert!(request.head.starts_with("POST /2023/day/3/answer "));
+    assert_eq!(request.header("cookie"), Some("session=secret"));
+    assert_eq!(request.body, "level=2&answer=4361");
+}
+
+#[test]
+fn reports_error_statuses() {
+    let (base_url, _requests) = serve(404, "Please don't repeatedly request this endpoint.");
+    let client = AocClient::new(&base_url, "secret", 2023);
+
+    assert!(matches!(
+        client.get_input(day!(25)),
+        Err(AocClientError::Status(404))
+    ));
+}